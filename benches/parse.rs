@@ -0,0 +1,174 @@
+//! Compares parsing a dump through an intermediate `serde_json::Value` with parsing
+//! it straight into a `DedupValue`, reporting time and peak heap usage of both.
+//!
+//! Uses the dump at `RAW_EXPLORER_BENCH_DUMP` if set, a generated one otherwise:
+//!
+//! ```sh
+//! RAW_EXPLORER_BENCH_DUMP=data-raw-dump.json cargo bench --bench parse
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Write,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use raw_explorer::util::DedupValue;
+
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+impl PeakAlloc {
+    fn grow(size: usize) {
+        let now = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new
+    }
+}
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc;
+
+const RUNS: u32 = 3;
+
+/// Fastest run and the largest heap growth over the allocations alive before the run.
+fn measure(f: impl Fn() -> DedupValue) -> (DedupValue, Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut peak = 0;
+    let mut result = DedupValue::Null;
+
+    for _ in 0..RUNS {
+        drop(result);
+        let base = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(base, Ordering::Relaxed);
+
+        let start = Instant::now();
+        result = f();
+        best = best.min(start.elapsed());
+        peak = peak.max(PEAK.load(Ordering::Relaxed) - base);
+    }
+
+    (result, best, peak)
+}
+
+/// Something shaped like `data.raw`: many prototypes per type that share most of their
+/// definitions (sounds, sprites, flags) and differ in a few properties.
+fn generated_dump() -> String {
+    const TYPES: usize = 80;
+    const PROTOTYPES: usize = 400;
+
+    let mut out = String::from("{");
+    for t in 0..TYPES {
+        if t > 0 {
+            out.push(',');
+        }
+        write!(out, r#""type-{t}":{{"#).unwrap();
+
+        for p in 0..PROTOTYPES {
+            if p > 0 {
+                out.push(',');
+            }
+            write!(
+                out,
+                r#""proto-{t}-{p}":{{"type":"type-{t}","name":"proto-{t}-{p}","order":"{order}","#,
+                order = p % 26,
+            )
+            .unwrap();
+            write!(
+                out,
+                r#""max_health":{health},"speed":{speed},"flags":["placeable-neutral","player-creation"],"#,
+                health = 100 + p % 7 * 50,
+                speed = f64::from(u32::try_from(p % 5).unwrap()) * 0.0125,
+            )
+            .unwrap();
+            write!(
+                out,
+                r#""working_sound":{{"sound":[{{"filename":"__base__/sound/type-{t}.ogg","volume":0.5}}],"audible_distance_modifier":0.7}},"#,
+            )
+            .unwrap();
+            write!(
+                out,
+                r#""picture":{{"layers":[{{"filename":"__base__/graphics/type-{t}/{variant}.png","width":64,"height":64,"shift":[0,-0.25],"scale":0.5}},{{"filename":"__base__/graphics/shadow.png","width":96,"height":48,"draw_as_shadow":true,"scale":0.5}}]}},"#,
+                variant = p % 3,
+            )
+            .unwrap();
+            write!(
+                out,
+                r#""collision_box":[[-0.4,-0.4],[0.4,0.4]],"selection_box":[[-0.5,-0.5],[0.5,0.5]],"localised_name":["entity-name.proto-{t}-{p}"]}}"#,
+            )
+            .unwrap();
+        }
+        out.push('}');
+    }
+    out.push('}');
+    out
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn mib(bytes: usize) -> f64 {
+    bytes as f64 / 1024.0 / 1024.0
+}
+
+fn main() {
+    let dump = std::env::var("RAW_EXPLORER_BENCH_DUMP").map_or_else(
+        |_| generated_dump(),
+        |path| std::fs::read_to_string(path).expect("failed to read the dump"),
+    );
+    println!("dump: {:.1} MiB of JSON", mib(dump.len()));
+
+    let (via_value, value_time, value_peak) = measure(|| {
+        DedupValue::from(serde_json::from_str::<serde_json::Value>(&dump).expect("invalid dump"))
+    });
+    let (streamed, stream_time, stream_peak) =
+        measure(|| serde_json::from_str::<DedupValue>(&dump).expect("invalid dump"));
+
+    println!("{:<20}{:>12}{:>16}", "", "time", "peak heap");
+    println!(
+        "{:<20}{:>9} ms{:>12.1} MiB",
+        "via serde_json",
+        value_time.as_millis(),
+        mib(value_peak)
+    );
+    println!(
+        "{:<20}{:>9} ms{:>12.1} MiB",
+        "streaming",
+        stream_time.as_millis(),
+        mib(stream_peak)
+    );
+
+    assert_eq!(via_value, streamed, "both ways must build the same tree");
+    assert!(
+        stream_peak < value_peak,
+        "streaming should not need more memory than going through serde_json::Value"
+    );
+}
@@ -0,0 +1,16 @@
+// Downloads and parses dumps off the main thread, see `src/util/dump_worker.rs`.
+// Runs the same wasm bundle as the page, only without hydrating anything.
+import init, { run_dump_job } from "/pkg/raw-explorer.js";
+
+const ready = init({ module_or_path: "/pkg/raw-explorer.wasm" });
+
+self.onmessage = async (ev) => {
+    try {
+        await ready;
+        await run_dump_job(ev.data);
+    } catch (e) {
+        // a rejection here never reaches the page, anything but a result makes it load the dump itself
+        self.postMessage(null);
+        throw e;
+    }
+};
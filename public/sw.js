@@ -0,0 +1,82 @@
+// Keeps the explorer usable without a network, registered from `src/util/offline.rs`.
+// Dumps and API docs are kept in IndexedDB by the app itself, this only caches the
+// WASM / JS / CSS bundle and the pages visited last.
+
+const CACHE = "raw-explorer-v1";
+
+// relies on `hash-files = false`, hashed names would have to be read from `hash.txt`
+const BUNDLE = [
+    "/pkg/raw-explorer.js",
+    "/pkg/raw-explorer.wasm",
+    "/pkg/raw-explorer.css",
+    "/dump-worker.js",
+    "/manifest.webmanifest",
+    "/icon.svg",
+    "/icon-192.png",
+    "/icon-512.png",
+];
+
+// pages are cached as they are visited, those visited least recently are dropped
+const MAX_PAGES = 20;
+
+self.addEventListener("install", (ev) => {
+    ev.waitUntil(
+        caches
+            .open(CACHE)
+            .then((cache) => cache.addAll(BUNDLE))
+            .then(() => self.skipWaiting()),
+    );
+});
+
+self.addEventListener("activate", (ev) => {
+    ev.waitUntil(
+        caches
+            .keys()
+            .then((keys) => Promise.all(keys.filter((key) => key !== CACHE).map((key) => caches.delete(key))))
+            .then(() => self.clients.claim()),
+    );
+});
+
+self.addEventListener("fetch", (ev) => {
+    const req = ev.request;
+    const url = new URL(req.url);
+
+    // resolver dumps are cross origin and server functions are POST requests,
+    // everything else the app loads is either a page or part of the bundle
+    const isPage = req.mode === "navigate";
+    if (req.method !== "GET" || url.origin !== self.location.origin || !(isPage || BUNDLE.includes(url.pathname))) {
+        return;
+    }
+
+    // network first so pages and bundle always match the server, the cache is only a fallback
+    ev.respondWith(
+        fetch(req)
+            .then((resp) => {
+                if (resp.ok) {
+                    ev.waitUntil(remember(req, resp.clone(), isPage));
+                }
+                return resp;
+            })
+            .catch(() => fromCache(req, isPage)),
+    );
+});
+
+async function remember(req, resp, isPage) {
+    const cache = await caches.open(CACHE);
+    // replacing an entry moves it to the end of the keys
+    await cache.delete(req);
+    await cache.put(req, resp);
+
+    if (!isPage) {
+        return;
+    }
+
+    const pages = (await cache.keys()).filter((key) => !BUNDLE.includes(new URL(key.url).pathname));
+    await Promise.all(pages.slice(0, Math.max(0, pages.length - MAX_PAGES)).map((key) => cache.delete(key)));
+}
+
+async function fromCache(req, isPage) {
+    // the query only selects what is shown in the explorer, any visit of the page will do
+    const cached = await caches.match(req, { ignoreSearch: isPage });
+    return cached ?? Response.error();
+}
@@ -4,15 +4,36 @@ use fapi_diff::format::prototype::PrototypeDoc;
 use leptos::prelude::*;
 use leptos_icons::Icon;
 use leptos_meta::{HashedStylesheet, MetaTags, Title, provide_meta_context};
-use leptos_router::{components::*, hooks::use_params, params::Params, path};
+use leptos_router::{
+    SsrMode,
+    components::*,
+    hooks::{use_location, use_navigate, use_params, use_query_map},
+    params::Params,
+    path,
+};
 use leptos_use::{UseClipboardReturn, use_clipboard};
+use wasm_bindgen::JsCast;
 
 use crate::{
-    components::{GitHubCorner, ModSelector, TypeDisplayMode, TypeDisplayModeSwitcher, TypeLink},
-    util::{DedupValue, TypeHelper, get_dump},
+    components::{
+        BookmarkSidebar, BookmarkToggle, Breadcrumbs, CommandPalette, CompareView,
+        DumpOverviewView, DumpUpload, ExpandControls, GitHubCorner, LoadErrorView, LoadProgress,
+        ModSelector, PropertyStatsPanel, QueryPanel, RecentList, SearchRedirect, TypeDisplayMode,
+        TypeDisplayModeSwitcher, TypeLink, UploadedDump, UploadedDumpStore, compare_href,
+        focus_href, get_available_mods, stats_href, view_href,
+    },
+    util::{
+        Baseline, BookmarkStore, Change, DedupValue, Dump, DumpOrigin, DumpProgress,
+        ExpansionStore, HistoryStore, LoadError, NodePath, NodeSpec, PublicConfig, Selection,
+        TypeHelper, Visit, WEB_MANIFEST, get_dump, get_public_config, preview, property_pattern,
+        prototype_count, register_service_worker, use_history, use_local_dump_changes,
+        use_public_config,
+    },
 };
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
+    let config = use_context::<PublicConfig>().unwrap_or_default();
+
     view! {
         <!DOCTYPE html>
         <html lang="en">
@@ -20,7 +41,11 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                 <meta charset="utf-8"/>
                 <meta name="viewport" content="width=device-width, initial-scale=1"/>
                 <meta name="format-detection" content="telephone=no"/>
-                <link rel="preconnect" href="https://modname_resolver.bpbin.com"/>
+                <link rel="preconnect" href=config.resolver_url/>
+                <link rel="manifest" href=WEB_MANIFEST/>
+                <link rel="icon" href="/icon.svg" type="image/svg+xml"/>
+                <link rel="apple-touch-icon" href="/icon-192.png"/>
+                <meta name="theme-color" content="#2e2b2a"/>
                 <HashedStylesheet id="main" options=options.clone() />
                 <AutoReload options=options.clone() />
                 <HydrationScripts options/>
@@ -37,6 +62,15 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
 pub fn App() -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
     provide_meta_context();
+    provide_context(UploadedDumpStore::new());
+    provide_context(HistoryStore::new());
+    provide_context(BookmarkStore::new());
+    provide_context(ExpansionStore::new());
+    provide_context(Resource::new(
+        || (),
+        async |_| get_public_config().await.unwrap_or_default(),
+    ));
+    Effect::new(|_| register_service_worker());
 
     view! {
         <Title text="data.raw explorer"/>
@@ -46,8 +80,12 @@ pub fn App() -> impl IntoView {
             <main>
                 <Routes fallback=|| "Page not found.".into_view()>
                     <Route path=path!("/") view=HomePage />
+                    <Route path=path!("/upload") view=UploadExplorer />
+                    <Route path=path!("/search") view=SearchRedirect ssr=SsrMode::Async />
+                    <Route path=path!("/compare") view=CompareView />
                     <ParentRoute path=path!("/e") view=VariantSelector>
                         <Route path=path!(":variant") view=Explorer />
+                        <Route path=path!(":variant/:version") view=Explorer />
                     </ParentRoute>
                 </Routes>
             </main>
@@ -60,12 +98,15 @@ fn HomePage() -> impl IntoView {
     view! {
         <ModSelector/>
         <p>"Select a mod to explore its data.raw dump"</p>
+        <RecentList/>
+        <DumpUpload/>
     }
 }
 
 #[derive(Params, PartialEq)]
 pub struct VariantParams {
     pub variant: Option<String>,
+    pub version: Option<String>,
 }
 
 #[component]
@@ -87,38 +128,722 @@ fn Explorer() -> impl IntoView {
             .and_then(|p| p.variant.clone())
             .expect("variant is required")
     };
-    let dump = LocalResource::new(move || get_dump(variant()));
+    let version = move || params.read().as_ref().ok().and_then(|p| p.version.clone());
+    let query = use_query_map();
+    let compare_to = move || query.read().get("base");
+    let dependency = move || query.read().get("dep");
+
+    let config = use_public_config();
+    let progress = RwSignal::new(DumpProgress::default());
+    let dump = LocalResource::new(move || {
+        let variant = variant();
+        let version = version();
+        async move {
+            get_dump(
+                config.await.resolver(),
+                variant,
+                version,
+                Some(progress.write_only()),
+            )
+            .await
+        }
+    });
+    let base_dump = LocalResource::new(move || {
+        let variant = variant();
+        let base = compare_to();
+        let dependency = dependency();
+        async move {
+            match (base, dependency) {
+                (Some(base), _) => {
+                    Some(get_dump(config.await.resolver(), variant, Some(base), None).await)
+                }
+                (None, Some(dep)) => Some(get_dump(config.await.resolver(), dep, None, None).await),
+                (None, None) => None,
+            }
+        }
+    });
 
     let type_mode = RwSignal::new(TypeDisplayMode::Normal);
-    let api_docs = Resource::new(|| (), async |_| get_api_docs().await);
+    let api_docs = LocalResource::new(load_api_docs);
+
+    provide_context(CurrentVariant {
+        variant: Signal::derive(variant),
+        version: Signal::derive(version),
+    });
+
+    let history = use_history();
+    provide_context(NodeOpened(Callback::new(move |path| {
+        history.visit(Visit::Path {
+            variant: variant(),
+            path,
+        });
+    })));
+    Effect::new(move |_| {
+        let count = dump.with(|d| match d {
+            Some(Ok(d)) => Some(prototype_count(&d.data)),
+            _ => None,
+        });
+        if let Some(count) = count {
+            history.visit(Visit::Variant {
+                variant: variant(),
+                version: version(),
+            });
+            if version().is_none() {
+                history.counted(variant(), count);
+            }
+        }
+    });
+
+    // reload local dumps when they get re-dumped on the server
+    let is_local = Memo::new(move |_| {
+        dump.get()
+            .is_some_and(|d| d.is_ok_and(|d| d.origin == DumpOrigin::Local))
+    });
+    use_local_dump_changes(
+        move || is_local.get(),
+        move |changed| {
+            if changed == variant() {
+                dump.refetch();
+            }
+        },
+    );
+
+    // the last loaded dump of the variant is the baseline to highlight changes after a reload
+    let loaded = StoredValue::new(None::<((String, Option<String>), DedupValue, Baseline)>);
 
     view! {
         <TypeDisplayModeSwitcher type_mode=type_mode />
-        <Suspense fallback=move || view! { <p>"Loading..."</p> }>
+        <DocsError api_docs=api_docs />
+        <CompareBanner
+            variant=Signal::derive(variant)
+            version=Signal::derive(version)
+            base=Signal::derive(compare_to)
+        />
+        <Show when=move || compare_to().is_none()>
+            <ProvenanceBanner variant=Signal::derive(variant) dependency=Signal::derive(dependency) />
+        </Show>
+        <BookmarkSidebar />
+        <Transition fallback=move || view! { <LoadProgress progress=progress /> }>
           {move || Suspend::new(async move {
             match dump.await {
-                Ok(data) => {
-                    let doc = api_docs.get().and_then(|d| d.ok().map(TypeHelper::new));
+                Ok(Dump { data, offline, .. }) => {
+                    let docs_url = config.await.docs_url;
+                    let doc = api_docs
+                        .get()
+                        .and_then(|d| d.ok().map(|d| TypeHelper::new(d, &docs_url)));
+
+                    let compared = match base_dump.await {
+                        Some(Ok(base)) => Some(Baseline::Value(base.data)),
+                        Some(Err(e)) => {
+                            return view! {
+                                <LoadErrorView error=e retry=move |()| base_dump.refetch() />
+                            }
+                            .into_any();
+                        }
+                        None => None,
+                    };
+
+                    let current = (variant(), version());
+                    let baseline = compared.unwrap_or_else(|| {
+                        loaded
+                            .try_update_value(|loaded| {
+                                let baseline = match loaded.take() {
+                                    Some((v, last, baseline))
+                                        if v == current && last.ptr_eq(&data) => baseline,
+                                    Some((v, last, _)) if v == current => Baseline::Value(last),
+                                    _ => Baseline::None,
+                                };
+                                *loaded = Some((current, data.clone(), baseline.clone()));
+                                baseline
+                            })
+                            .unwrap_or_default()
+                    });
+
                     view! {
-                        <JsonViewer val=data doc=doc type_mode=type_mode.read_only() start_open=true/>
+                        <Show when=move || offline>
+                            <OfflineBanner retry=move |()| dump.refetch() />
+                        </Show>
+                        <DumpView
+                            data=data
+                            doc=doc
+                            type_mode=type_mode.read_only()
+                            baseline=baseline
+                        />
                     }.into_any()
                 },
-                Err(e) => view! { <p>{e}</p> }.into_any(),
+                Err(e) => view! {
+                    <LoadErrorView error=e retry=move |()| dump.refetch() />
+                }.into_any(),
             }
           })}
-        </Suspense>
+        </Transition>
+    }
+}
+
+#[component]
+fn CompareBanner(
+    variant: Signal<String>,
+    version: Signal<Option<String>>,
+    base: Signal<Option<String>>,
+) -> impl IntoView {
+    move || {
+        let base = base.get()?;
+        let variant = variant.get();
+        let (label, href) = match version.get() {
+            Some(version) => (
+                format!("{variant} {version}"),
+                format!("/e/{variant}/{version}"),
+            ),
+            None => (variant.clone(), format!("/e/{variant}")),
+        };
+
+        Some(view! {
+            <p class="compare-banner">
+                "Comparing " <code>{label}</code> " against " <code>{base}</code> ". "
+                <A href=href>"Stop comparing"</A>
+            </p>
+        })
+    }
+}
+
+/// Tells that the server could not be reached and the dump is the copy kept in the browser.
+#[component]
+fn OfflineBanner(#[prop(into)] retry: Callback<()>) -> impl IntoView {
+    view! {
+        <p class="offline-banner" role="status">
+            "Offline: showing the copy of this dump saved in your browser, it might be outdated. "
+            <button on:click=move |_| retry.run(())>"Try again"</button>
+        </p>
+    }
+}
+
+/// Offers to mark what the mod changed compared to the dump of its dependency.
+#[component]
+fn ProvenanceBanner(variant: Signal<String>, dependency: Signal<Option<String>>) -> impl IntoView {
+    let config = use_public_config();
+    let location = use_location();
+    let navigate = use_navigate();
+    let mods = LocalResource::new(get_available_mods);
+    let href = move |changes: &[(&str, Option<&str>)]| {
+        let mut query = location.query.get();
+        for (key, value) in changes {
+            query.remove(key);
+            if let Some(value) = value {
+                query.insert(key.to_string(), (*value).to_string());
+            }
+        }
+        format!("{}{}", location.pathname.get(), query.to_query_string())
+    };
+
+    move || {
+        let variant = variant.get();
+        let Some(dep) = dependency.get() else {
+            let known = config
+                .get()
+                .and_then(|c| c.dependency(&variant).map(ToString::to_string));
+            if let Some(known) = known {
+                return Some(
+                    view! {
+                        <p class="provenance-banner">
+                            <code>{variant}</code> " is based on " <code>{known.clone()}</code> ". "
+                            <A href=href(&[("dep", Some(known.as_str()))])>"Show what it changes"</A>
+                        </p>
+                    }
+                    .into_any(),
+                );
+            }
+
+            // mods without a configured dependency can still be compared against any dump
+            let options = mods
+                .get()?
+                .ok()?
+                .iter()
+                .filter(|m| m.name != variant)
+                .map(|m| view! { <option value=m.name.clone()>{m.name.clone()}</option> })
+                .collect_view();
+            let on_change = {
+                let href = href.clone();
+                let navigate = navigate.clone();
+                move |ev: leptos::ev::Event| {
+                    let dep = event_target_value(&ev);
+                    if !dep.is_empty() {
+                        navigate(&href(&[("dep", Some(dep.as_str()))]), Default::default());
+                    }
+                }
+            };
+            return Some(
+                view! {
+                    <p class="provenance-banner">
+                        "Show what " <code>{variant}</code> " changes compared to "
+                        <select on:change=on_change>
+                            <option value="" selected>"pick a dump"</option>
+                            {options}
+                        </select>
+                    </p>
+                }
+                .into_any(),
+            );
+        };
+
+        let only_changes = location
+            .query
+            .read()
+            .get("changes")
+            .is_some_and(|c| c == "only");
+        let toggle = if only_changes {
+            view! { <A href=href(&[("changes", None)])>"show everything"</A> }
+        } else {
+            view! { <A href=href(&[("changes", Some("only"))])>"only show changes"</A> }
+        };
+
+        Some(
+            view! {
+                <p class="provenance-banner">
+                    "Showing what " <code>{variant}</code> " changes compared to " <code>{dep}</code> ": "
+                    <span class="modified">"modified"</span> ", "
+                    <span class="added">"added"</span> ", "
+                    <span class="removed">"removed"</span> ". "
+                    {toggle} " | "
+                    <A href=href(&[("dep", None), ("changes", None)])>"hide"</A>
+                </p>
+            }
+            .into_any(),
+        )
+    }
+}
+
+/// Explains why type annotations are missing.
+#[component]
+fn DocsError(api_docs: LocalResource<Result<PrototypeDoc, LoadError>>) -> impl IntoView {
+    move || match api_docs.get() {
+        Some(Err(e)) => view! {
+            <LoadErrorView error=e retry=move |()| api_docs.refetch() />
+        }
+        .into_any(),
+        _ => ().into_any(),
+    }
+}
+
+#[component]
+fn UploadExplorer() -> impl IntoView {
+    let uploaded = expect_context::<UploadedDumpStore>().0;
+    let config = use_public_config();
+
+    let type_mode = RwSignal::new(TypeDisplayMode::Normal);
+    let api_docs = LocalResource::new(load_api_docs);
+
+    view! {
+        <ModSelector/>
+        <TypeDisplayModeSwitcher type_mode=type_mode />
+        <DocsError api_docs=api_docs />
+        <Transition fallback=move || view! { <p>"Loading..."</p> }>
+          {move || Suspend::new(async move {
+            let docs_url = config.await.docs_url;
+            let doc = api_docs.await.ok().map(|d| TypeHelper::new(d, &docs_url));
+            match uploaded.get() {
+                Some(UploadedDump { name, data }) => view! {
+                    <p>"Viewing " <code>{name}</code></p>
+                    <DumpView data=data doc=doc type_mode=type_mode.read_only() />
+                }.into_any(),
+                None => view! {
+                    <p>"No dump opened, " <A href="/">"open one from your machine"</A> " first."</p>
+                }.into_any(),
+            }
+          })}
+        </Transition>
+    }
+}
+
+/// A dump as a tree with the breadcrumb bar, the bulk actions and the focus mode.
+///
+/// `?focus=<path>` re-roots the tree at that node, `?path=<path>` opens and selects it.
+#[component]
+fn DumpView(
+    data: DedupValue,
+    #[prop(optional_no_strip)] doc: Option<TypeHelper>,
+    type_mode: ReadSignal<TypeDisplayMode>,
+    #[prop(optional)] baseline: Baseline,
+) -> impl IntoView {
+    let query = use_query_map();
+    let focus = Memo::new(move |_| {
+        query
+            .read()
+            .get("focus")
+            .map(|p| NodePath::parse(&p))
+            .filter(|p| !p.is_root())
+    });
+    let reveal = Memo::new(move |_| query.read().get("path").map(|p| NodePath::parse(&p)));
+    let only_changes = Memo::new(move |_| query.read().get("changes").is_some_and(|c| c == "only"));
+    // without a node to show the overview is a better start than the collapsed root
+    let overview = Memo::new(move |_| {
+        let query = query.read();
+        match query.get("view").as_deref() {
+            Some(view) => view == "overview",
+            None => query.get("path").is_none() && query.get("focus").is_none(),
+        }
+    });
+
+    let expansion = expect_context::<ExpansionStore>();
+    let selection = Selection::new();
+    provide_context(selection);
+
+    let tree_ref = NodeRef::<leptos::html::Div>::new();
+    let UseClipboardReturn { copy, .. } = use_clipboard();
+    let root = data.clone();
+    let palette = view! { <CommandPalette data=data.clone() doc=doc.clone() /> };
+    let query_panel = view! { <QueryPanel data=data.clone() /> };
+    let stats_panel = view! { <PropertyStatsPanel data=data.clone() /> };
+    let overview_view = {
+        let data = data.clone();
+        let doc = doc.clone();
+        move || {
+            overview
+                .get()
+                .then(|| view! { <DumpOverviewView data=data.clone() doc=doc.clone() /> })
+        }
+    };
+    let location = use_location();
+    let overview_href = {
+        let location = location.clone();
+        move || view_href(&location, "overview")
+    };
+    let tree_href = move || view_href(&location, "tree");
+    let tabs = view! {
+        <nav class="view-tabs">
+            <span class:active=move || overview.get()>
+                <A href=overview_href>"overview"</A>
+            </span>
+            <span class:active=move || !overview.get()>
+                <A href=tree_href>"tree"</A>
+            </span>
+        </nav>
+    };
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        if ev.ctrl_key() || ev.meta_key() || ev.alt_key() {
+            return;
+        }
+
+        // typing into the note / depth inputs keeps its keys
+        let target = event_target::<web_sys::Element>(&ev);
+        if matches!(target.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT") {
+            return;
+        }
+
+        let Some(tree) = tree_ref.get() else {
+            return;
+        };
+        // rows hidden in the changes only mode can't take the focus
+        let selector = if only_changes.get_untracked() {
+            "[role=treeitem]:not(.unchanged)"
+        } else {
+            "[role=treeitem]"
+        };
+        let Ok(items) = tree.query_selector_all(selector) else {
+            return;
+        };
+        let current = target.closest("[role=treeitem]").ok().flatten();
+        let idx = current.as_ref().and_then(|current| {
+            let current: &web_sys::Node = current;
+            (0..items.length()).find(|&i| items.get(i).as_ref() == Some(current))
+        });
+
+        let focus = |node: Option<web_sys::Node>| {
+            if let Some(el) = node.and_then(|n| n.dyn_into::<web_sys::HtmlElement>().ok()) {
+                let _ = el.focus();
+            }
+        };
+
+        match ev.key().as_str() {
+            "ArrowDown" => focus(items.get(idx.map_or(0, |i| i + 1))),
+            "ArrowUp" => focus(
+                idx.and_then(|i| i.checked_sub(1))
+                    .and_then(|i| items.get(i)),
+            ),
+            "Home" => focus(items.get(0)),
+            "End" => focus(items.get(items.length().saturating_sub(1))),
+            // only reaches the tree for open nodes, moves to the first child
+            "ArrowRight" => {
+                let next = idx.and_then(|i| items.get(i + 1));
+                if let Some(current) = &current
+                    && next.as_ref().is_some_and(|n| current.contains(Some(n)))
+                {
+                    focus(next);
+                }
+            }
+            // only reaches the tree for collapsed nodes and values, moves to the parent
+            "ArrowLeft" => focus(
+                current
+                    .and_then(|c| c.parent_element())
+                    .and_then(|p| p.closest("[role=treeitem]").ok().flatten())
+                    .map(Into::into),
+            ),
+            "/" => focus(document().get_element_by_id("mod-search").map(Into::into)),
+            "y" => {
+                if let Some(path) = selection.get_untracked() {
+                    copy(&path.to_string());
+                }
+            }
+            "c" => {
+                if let Some(val) = selection
+                    .get_untracked()
+                    .and_then(|path| root.get_path(&path).cloned())
+                {
+                    copy(&serde_json::to_string_pretty(&val).unwrap_or_default());
+                }
+            }
+            _ => return,
+        }
+
+        ev.prevent_default();
+    };
+
+    // rendered rows pick up the opened path through the store
+    Effect::new(move |_| {
+        if let Some(reveal) = reveal.get() {
+            expansion.reveal(&reveal);
+            selection.reveal(reveal);
+        }
+    });
+
+    let tree = move || {
+        let path = focus.get().unwrap_or_default();
+        let Some(val) = data.get_path(&path).cloned() else {
+            return view! {
+                <p>"There is nothing at " <code>{path.to_string()}</code> " in this dump."</p>
+            }
+            .into_any();
+        };
+
+        let doc = doc_at(doc.clone(), &data, &path);
+        let baseline = path
+            .segments()
+            .iter()
+            .fold(baseline.clone(), |b, key| b.child(key));
+
+        view! {
+            <ExpandControls path=path.clone() val=val.clone() toolbar=true />
+            <JsonViewer
+                key=path.last().cloned().unwrap_or_default()
+                val=val
+                doc=doc
+                type_mode=type_mode
+                path=path
+                baseline=baseline
+                start_open=true
+            />
+        }
+        .into_any()
+    };
+
+    view! {
+        {palette}
+        {query_panel}
+        {stats_panel}
+        {tabs}
+        {overview_view}
+        <div class="tree-view" hidden=move || overview.get()>
+            <Breadcrumbs
+                selected=Signal::derive(move || selection.get())
+                focus=focus.into()
+                on_select=move |path| selection.reveal(path)
+            />
+            <div
+                class="json-tree"
+                class:changes-only=move || only_changes.get()
+                role="tree"
+                aria-label="data.raw"
+                node_ref=tree_ref
+                tabindex=move || selection.get().is_none().then_some("0")
+                on:focus=move |_| {
+                    // nothing selected yet, start at the first row
+                    if let Some(first) = tree_ref
+                        .get()
+                        .and_then(|tree| tree.query_selector("[role=treeitem]").ok().flatten())
+                        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+                    {
+                        let _ = first.focus();
+                    }
+                }
+                on:keydown=on_keydown
+            >
+                {tree}
+            </div>
+        </div>
+    }
+}
+
+/// The docs of the node at `path`, traversed the same way [`JsonViewer`] does.
+pub(crate) fn doc_at(
+    doc: Option<TypeHelper>,
+    root: &DedupValue,
+    path: &NodePath,
+) -> Option<TypeHelper> {
+    let mut doc = doc?;
+    let mut val = root;
+    for key in path.segments() {
+        doc = match val {
+            DedupValue::Array(arr) => doc.traverse_idx(key.parse().ok()?, arr.len()),
+            _ => doc.traverse_prop(key),
+        };
+        val = val.child(key)?;
     }
+
+    Some(doc)
 }
 
+/// Variant and version shown by the explorer, nodes of uploaded dumps can't be bookmarked.
+#[derive(Clone, Copy)]
+struct CurrentVariant {
+    variant: Signal<String>,
+    version: Signal<Option<String>>,
+}
+
+/// Called when the user opens a node at prototype depth or below, bulk actions don't count.
+#[derive(Clone, Copy)]
+struct NodeOpened(Callback<NodePath>);
+
 #[component]
 fn JsonViewer(
     #[prop(optional)] key: Arc<str>,
     val: DedupValue,
     #[prop(optional_no_strip)] doc: Option<TypeHelper>,
     type_mode: ReadSignal<TypeDisplayMode>,
+    #[prop(optional)] path: NodePath,
+    #[prop(optional)] baseline: Baseline,
+    /// The node only exists in the baseline.
+    #[prop(optional)]
+    removed: bool,
     #[prop(optional)] start_open: bool,
 ) -> impl IntoView {
-    let (open, set_open) = RwSignal::new(start_open).split();
+    let expansion = use_context::<ExpansionStore>();
+    let (open, set_open) =
+        RwSignal::new(start_open || expansion.is_some_and(|e| e.is_open(&path))).split();
+
+    if let Some(expansion) = expansion
+        && matches!(val, DedupValue::Array(_) | DedupValue::Object(_))
+    {
+        let path = path.clone();
+        Effect::new(move |_| expansion.set_open(path.clone(), open.get()));
+
+        // pick up bulk expand / collapse actions
+        let path = path.clone();
+        Effect::new(move |prev: Option<()>| {
+            expansion.track();
+            let is_open = expansion.is_open(&path);
+            if prev.is_some() && open.get_untracked() != is_open {
+                set_open.set(is_open);
+            }
+        });
+    }
+
+    let row_ref = NodeRef::<leptos::html::Div>::new();
+    let selection = use_context::<Selection>();
+    let selected = {
+        let path = path.clone();
+        Signal::derive(move || selection.is_some_and(|s| s.is(&path)))
+    };
+    if let Some(selection) = selection {
+        Effect::new(move |_| {
+            if selected.get()
+                && selection.take_scroll()
+                && let Some(row) = row_ref.get()
+            {
+                row.scroll_into_view();
+            }
+        });
+    }
+
+    let on_select = {
+        let path = path.clone();
+        move |ev: leptos::ev::MouseEvent| {
+            // only the innermost row gets selected
+            ev.stop_propagation();
+            if let Some(selection) = selection {
+                selection.select(path.clone());
+            }
+        }
+    };
+    let on_focus = {
+        let path = path.clone();
+        move |_| {
+            if let Some(selection) = selection {
+                selection.select(path.clone());
+            }
+        }
+    };
+
+    // everything else is handled for the whole tree by the `DumpView`
+    let level = path.depth() + 1;
+    let is_container = matches!(val, DedupValue::Array(_) | DedupValue::Object(_));
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let is_own_row = row_ref.get().is_some_and(|row| {
+            let row: &web_sys::Element = &row;
+            event_target::<web_sys::Element>(&ev) == *row
+        });
+        if !is_container || !is_own_row {
+            return;
+        }
+
+        let open = open.get_untracked();
+        match ev.key().as_str() {
+            "ArrowRight" if !open => set_open.set(true),
+            "ArrowLeft" if open => set_open.set(false),
+            "Enter" | " " => set_open.set(!open),
+            _ => return,
+        }
+
+        ev.prevent_default();
+        ev.stop_propagation();
+    };
+
+    let aria_label = {
+        let value = match &val {
+            DedupValue::Null => "null".to_string(),
+            DedupValue::Bool(b) => b.to_string(),
+            DedupValue::Number(n) => n.to_string(),
+            DedupValue::String(s) => format!("\"{s}\""),
+            DedupValue::Array(_) | DedupValue::Object(_) => preview(&val).unwrap_or_default(),
+        };
+        let mut label = if key.is_empty() {
+            value
+        } else {
+            format!("{key}: {value}")
+        };
+        if let Some(t) = doc.as_ref().and_then(TypeHelper::describe) {
+            label.push_str(&format!(", type {t}"));
+        }
+
+        label
+    };
+
+    let bookmark = use_context::<CurrentVariant>()
+        .map(|v| view! { <BookmarkToggle variant=v.variant path=path.clone() /> });
+    let stats = property_pattern(&path)
+        .filter(|_| !is_container)
+        .map(|pattern| {
+            let location = use_location();
+            let navigate = use_navigate();
+            view! {
+                <button
+                    class="stats"
+                    title="statistics across prototypes of this type"
+                    on:click=move |ev| {
+                        ev.stop_propagation();
+                        navigate(&stats_href(&location, Some(&pattern)), Default::default());
+                    }
+                >
+                    <Icon icon={icondata::BsBarChart} width="0.875rem" height="0.875rem" />
+                </button>
+            }
+        });
+    let marker = view! { {bookmark} {stats} };
+
+    let class = match baseline.change(&val).map(Change::class) {
+        _ if removed => "json-row removed".to_string(),
+        Some(change) if !change.is_empty() => format!("json-row {change}"),
+        _ => "json-row".to_string(),
+    };
 
     let row = match val {
         DedupValue::Null => view! { <JsonKV
@@ -128,6 +853,7 @@ fn JsonViewer(
                 type_mode=type_mode
                 val="null".into()
             />
+            {marker}
         }
         .into_any(),
         DedupValue::Bool(b) => view! { <JsonKV
@@ -137,6 +863,7 @@ fn JsonViewer(
                 type_mode=type_mode
                 val=b.to_string()
             />
+            {marker}
         }
         .into_any(),
         DedupValue::Number(n) => view! { <JsonKV
@@ -146,6 +873,7 @@ fn JsonViewer(
             type_mode=type_mode
             val=n.to_string()
             />
+            {marker}
         }
         .into_any(),
         DedupValue::String(s) => view! { <JsonKV
@@ -155,22 +883,27 @@ fn JsonViewer(
             type_mode=type_mode
             val=format!("\"{s}\"")
             />
+            {marker}
         }
         .into_any(),
         DedupValue::Array(arr) => {
             let len = arr.len();
             let raw = arr.clone();
+            let header_path = path.clone();
             let d = doc.clone();
             let children = move || {
                 open.get().then(|| {
                     arr.iter()
                         .enumerate()
                         .map(|(idx, v)| {
+                            let key: Arc<str> = idx.to_string().into();
                             view! {
                                 <JsonViewer
-                                    key=idx.to_string().into()
+                                    key=key.clone()
                                     doc=d.clone().map(|d| d.traverse_idx(idx, len))
                                     type_mode=type_mode
+                                    path=path.child(key.clone())
+                                    baseline=baseline.child(&key)
                                     val=v.clone()
                                 />
                             }
@@ -184,10 +917,13 @@ fn JsonViewer(
                     key=key
                     doc=doc
                     type_mode=type_mode
+                    path=header_path
+                    open=open
                     write=set_open
                     raw=DedupValue::Array(raw)
                 />
-                <div class="json-children">
+                {marker}
+                <div class="json-children" role="group">
                     {children}
                 </div>
             }
@@ -195,21 +931,47 @@ fn JsonViewer(
         }
         DedupValue::Object(obj) => {
             let raw = obj.clone();
+            let header_path = path.clone();
             let d = doc.clone();
             let children = move || {
                 open.get().then(|| {
-                    obj.iter()
+                    let children = obj
+                        .iter()
                         .map(|(k, v)| {
                             view! {
                                 <JsonViewer
                                     key=k.clone()
                                     doc=d.clone().map(|d| d.traverse_prop(k))
                                     type_mode=type_mode
+                                    path=path.child(k.clone())
+                                    baseline=baseline.child(k)
                                     val=v.clone()
                                 />
                             }
                         })
-                        .collect_view()
+                        .collect_view();
+
+                    let removed = match &baseline {
+                        Baseline::Value(DedupValue::Object(base)) => base
+                            .iter()
+                            .filter(|(k, _)| !obj.contains_key(*k))
+                            .map(|(k, v)| {
+                                view! {
+                                    <JsonViewer
+                                        key=k.clone()
+                                        doc=d.clone().map(|d| d.traverse_prop(k))
+                                        type_mode=type_mode
+                                        path=path.child(k.clone())
+                                        removed=true
+                                        val=v.clone()
+                                    />
+                                }
+                            })
+                            .collect_view(),
+                        _ => Vec::new(),
+                    };
+
+                    (children, removed)
                 })
             };
 
@@ -218,10 +980,13 @@ fn JsonViewer(
                     key=key
                     doc=doc
                     type_mode=type_mode
+                    path=header_path
+                    open=open
                     write=set_open
                     raw=DedupValue::Object(raw)
                 />
-                <div class="json-children">
+                {marker}
+                <div class="json-children" role="group">
                     {children}
                 </div>
             }
@@ -230,7 +995,21 @@ fn JsonViewer(
     };
 
     view! {
-        <div class="json-row" class:expanded=open>
+        <div
+            class=class
+            class:expanded=open
+            class:selected=selected
+            node_ref=row_ref
+            role="treeitem"
+            aria-level=level
+            aria-expanded=move || is_container.then(|| open.get().to_string())
+            aria-selected=move || selected.get().to_string()
+            aria-label=aria_label
+            tabindex=move || if selected.get() { "0" } else { "-1" }
+            on:click=on_select
+            on:focus=on_focus
+            on:keydown=on_keydown
+        >
             {row}
         </div>
     }
@@ -273,15 +1052,51 @@ fn JsonCollapsibleHeader(
     #[prop(optional_no_strip)] doc: Option<TypeHelper>,
     type_mode: ReadSignal<TypeDisplayMode>,
     #[prop(optional)] val: String,
+    path: NodePath,
+    open: ReadSignal<bool>,
     write: WriteSignal<bool>,
     raw: DedupValue,
 ) -> impl IntoView {
     let UseClipboardReturn {
         is_supported, copy, ..
     } = use_clipboard();
+    let opened = use_context::<NodeOpened>();
+    let show_actions = RwSignal::new(false);
+    let actions_raw = raw.clone();
+    let location = use_location();
+    let summary = preview(&raw);
+    let compare = use_context::<CurrentVariant>().map(|CurrentVariant { variant, version }| {
+        let path = path.clone();
+        move || {
+            let spec = NodeSpec {
+                variant: variant.get(),
+                version: version.get(),
+                path: path.clone(),
+            };
+            compare_href(&spec, None)
+        }
+    });
+
+    let on_toggle = {
+        let path = path.clone();
+        move |_| {
+            let mut now_open = false;
+            write.update(|v| {
+                *v = !*v;
+                now_open = *v;
+            });
+
+            if now_open
+                && path.depth() >= 2
+                && let Some(NodeOpened(opened)) = opened
+            {
+                opened.run(path.clone());
+            }
+        }
+    };
 
     view! {
-        <a on:click=move |_| write.update(|v| *v = !*v)>
+        <a on:click=on_toggle>
             <span class="arrow"/>
             <JsonKV
                 key=key
@@ -290,6 +1105,10 @@ fn JsonCollapsibleHeader(
                 type_mode=type_mode
                 val=val
             />
+            {move || {
+                let summary = summary.clone().filter(|_| !open.get())?;
+                Some(view! { <span class="preview">{summary}</span> })
+            }}
         </a>
         <Show when=move || is_supported.get()>
             <button on:click={
@@ -300,38 +1119,101 @@ fn JsonCollapsibleHeader(
                 <Icon icon={icondata::FiCopy} width="1rem" height="1rem" />
             </button>
         </Show>
+        <button class="actions" title="expand / collapse" on:click=move |_| show_actions.update(|s| *s = !*s)>
+            <Icon icon={icondata::FiMoreHorizontal} width="1rem" height="1rem" />
+        </button>
+        <Show when=move || show_actions.get()>
+            <ExpandControls path=path.clone() val=actions_raw.clone() />
+            <span class="focus">
+                <A href={
+                    let location = location.clone();
+                    let path = path.clone();
+                    move || focus_href(&location, &path)
+                }>"focus"</A>
+            </span>
+            {compare.clone().map(|href| view! {
+                <span class="focus"><A href=href>"compare"</A></span>
+            })}
+        </Show>
     }
 }
 
+/// Version of the API docs served by [`get_api_docs`], the latest base version the resolver knows.
+#[cfg(feature = "ssr")]
+async fn api_docs_version() -> String {
+    use crate::util::{compare_versions, resolver_variants};
+
+    resolver_variants()
+        .await
+        .ok()
+        .and_then(|mods| {
+            mods.into_iter()
+                .filter_map(|(name, version)| (name == "base").then_some(version))
+                .max_by(|a, b| compare_versions(a, b))
+        })
+        .unwrap_or_else(|| "latest".to_string())
+}
+
+#[server]
+pub async fn get_api_docs_version() -> Result<String, LoadError> {
+    Ok(api_docs_version().await)
+}
+
 // api docs need to be fetched from the server side to avoid CORS issues :)
 #[server]
-pub async fn get_api_docs() -> Result<PrototypeDoc, ServerFnError> {
-    #[derive(serde::Deserialize)]
-    struct ProcessedMods {
-        #[serde(rename = "processed")]
-        raw: std::collections::BTreeSet<String>,
+pub async fn get_api_docs() -> Result<PrototypeDoc, LoadError> {
+    use crate::util::{PublicConfig, ServerCaches};
+
+    let config = use_context::<PublicConfig>().unwrap_or_default();
+    let base_version = api_docs_version().await;
+
+    let url = format!("{}/{base_version}/prototype-api.json", config.docs_url);
+    let fetch = || crate::util::fetch_data(&url, config.timeout());
+
+    match use_context::<ServerCaches>() {
+        Some(caches) => caches.docs.get_or_try_insert(&base_version, fetch).await,
+        None => fetch().await,
     }
+    .map_err(|e| LoadError::DocsUnavailable(e.to_string()))
+}
 
-    let base_version = crate::util::fetch_from_resolver::<ProcessedMods>("stats")
-        .await
-        .map_err(|_| ())
-        .and_then(|a| {
-            a.raw
-                .iter()
-                .find_map(|r| {
-                    if r.starts_with("base_") {
-                        Some(r.trim_start_matches("base_").to_string())
-                    } else {
-                        None
-                    }
-                })
-                .ok_or(())
-        })
-        .unwrap_or_else(|()| "latest".to_string());
+/// [`get_api_docs`], kept in the browser's IndexedDB until the server moves to another
+/// docs version and used as is when the server can't be reached.
+#[cfg(feature = "hydrate")]
+pub async fn load_api_docs() -> Result<PrototypeDoc, LoadError> {
+    use crate::util::{CacheEntry, IdbCache};
+
+    const KEY: &str = "docs";
+
+    let cache = IdbCache::open().await;
+    let cached = match &cache {
+        Some(cache) => cache.get(KEY).await,
+        None => None,
+    };
+
+    let version = get_api_docs_version().await.ok();
+    if let Some(docs) = cached
+        .filter(|cached| version.is_none() || cached.etag == version)
+        .and_then(|cached| serde_json::from_slice(&cached.data.to_vec()).ok())
+    {
+        return Ok(docs);
+    }
+
+    let docs = get_api_docs().await?;
+    if let (Some(cache), Some(version)) = (cache, version)
+        && let Ok(raw) = serde_json::to_vec(&docs)
+    {
+        let entry = CacheEntry {
+            etag: Some(version),
+            data: js_sys::Uint8Array::from(raw.as_slice()),
+        };
+        cache.put(KEY, &entry).await;
+    }
+
+    Ok(docs)
+}
 
-    crate::util::fetch_data(&format!(
-        "https://lua-api.factorio.com/{base_version}/prototype-api.json"
-    ))
-    .await
-    .map_err(ServerFnError::new)
+#[cfg(not(feature = "hydrate"))]
+pub async fn load_api_docs() -> Result<PrototypeDoc, LoadError> {
+    get_api_docs().await
 }
@@ -1,7 +1,33 @@
+mod bookmarks;
+mod breadcrumbs;
+mod command_palette;
+mod compare;
+mod dump_upload;
+mod expand_controls;
 mod gh_corner;
+mod load_error;
+mod load_progress;
+mod mod_search;
 mod mod_selector;
+mod overview;
+mod property_stats;
+mod query;
+mod recent;
 mod type_link;
 
+pub use bookmarks::*;
+pub use breadcrumbs::*;
+pub use command_palette::*;
+pub use compare::*;
+pub use dump_upload::*;
+pub use expand_controls::*;
 pub use gh_corner::*;
+pub use load_error::*;
+pub use load_progress::*;
+pub use mod_search::*;
 pub use mod_selector::*;
+pub use overview::*;
+pub use property_stats::*;
+pub use query::*;
+pub use recent::*;
 pub use type_link::*;
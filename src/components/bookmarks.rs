@@ -0,0 +1,133 @@
+use leptos::{ev, prelude::*, task::spawn_local};
+use leptos_icons::Icon;
+use leptos_router::{components::A, location::Url};
+
+use crate::util::{Bookmarks, LoadError, NodePath, use_bookmarks};
+
+/// Marker next to a node to bookmark it, filled while the node is bookmarked.
+#[component]
+pub fn BookmarkToggle(variant: Signal<String>, path: NodePath) -> impl IntoView {
+    let store = use_bookmarks();
+
+    let note = store.note(variant, path.clone());
+    let is_bookmarked = Memo::new(move |_| note.read().is_some());
+
+    let title = move || match note.get() {
+        Some(note) if !note.is_empty() => note,
+        Some(_) => "remove bookmark".to_string(),
+        None => "bookmark".to_string(),
+    };
+
+    view! {
+        <button
+            class="bookmark"
+            class:active=is_bookmarked
+            title=title
+            on:click=move |_| store.update(|b| b.toggle(&variant.read(), &path))
+        >
+            {move || if is_bookmarked.get() {
+                view! { <Icon icon={icondata::BsBookmarkFill} width="0.875rem" height="0.875rem" /> }
+            } else {
+                view! { <Icon icon={icondata::BsBookmark} width="0.875rem" height="0.875rem" /> }
+            }}
+        </button>
+    }
+}
+
+/// Lists all bookmarks with their notes and allows exporting / importing them.
+#[component]
+pub fn BookmarkSidebar() -> impl IntoView {
+    let store = use_bookmarks();
+    let bookmarks = store.get();
+    let import_error = RwSignal::new(None::<String>);
+
+    let export_href = move || {
+        let json = bookmarks.with(|b| serde_json::to_string_pretty(b).unwrap_or_default());
+        format!("data:application/json;charset=utf-8,{}", Url::escape(&json))
+    };
+
+    let on_import = move |ev: ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        let name = file.name();
+
+        spawn_local(async move {
+            let text = wasm_bindgen_futures::JsFuture::from(file.text())
+                .await
+                .ok()
+                .and_then(|t| t.as_string());
+
+            let Some(text) = text else {
+                import_error.set(Some(format!("{name}: could not read file")));
+                return;
+            };
+
+            match serde_json::from_str::<Bookmarks>(&text) {
+                Ok(imported) => {
+                    import_error.set(None);
+                    store.update(|b| b.merge(imported));
+                }
+                Err(e) => import_error.set(Some(format!("{name}: {}", LoadError::from(e)))),
+            }
+        });
+    };
+
+    view! {
+        <aside class="bookmark-sidebar">
+            <h3>"Bookmarks"</h3>
+            <div class="actions">
+                <a href=export_href download="raw-explorer-bookmarks.json">"export"</a>
+                <label>
+                    "import"
+                    <input type="file" accept=".json,application/json" on:change=on_import/>
+                </label>
+            </div>
+            {move || import_error.get().map(|e| view! { <p class="error">{e}</p> })}
+            <ul>
+                <For
+                    each=move || bookmarks.get().0
+                    key=|b| (b.variant.clone(), b.path.clone())
+                    let(bookmark)
+                >
+                    {
+                        let variant = bookmark.variant.clone();
+                        let path = bookmark.path.clone();
+                        let visit = bookmark.visit();
+
+                        view! {
+                            <li>
+                                <A href=visit.href()>{visit.label()}</A>
+                                <button
+                                    title="remove bookmark"
+                                    on:click={
+                                        let variant = variant.clone();
+                                        let path = path.clone();
+                                        move |_| store.update(|b| b.remove(&variant, &path))
+                                    }
+                                >
+                                    <Icon icon={icondata::FiX} width="0.875rem" height="0.875rem" />
+                                </button>
+                                <textarea
+                                    placeholder="note"
+                                    prop:value={
+                                        let variant = variant.clone();
+                                        let path = path.clone();
+                                        move || bookmarks.with(|b| {
+                                            b.get(&variant, &path).map(|b| b.note.clone()).unwrap_or_default()
+                                        })
+                                    }
+                                    on:change=move |ev| {
+                                        let note = event_target_value(&ev);
+                                        store.update(|b| b.set_note(&variant, &path, note));
+                                    }
+                                />
+                            </li>
+                        }
+                    }
+                </For>
+            </ul>
+        </aside>
+    }
+}
@@ -0,0 +1,86 @@
+use leptos::prelude::*;
+use leptos_router::{components::A, hooks::use_location, location::Location};
+
+use crate::util::NodePath;
+
+/// Link to the current page with the explorer focused on `path`, the root removes the focus.
+pub fn focus_href(location: &Location, path: &NodePath) -> String {
+    let mut query = location.query.get();
+    query.remove("focus");
+    if !path.is_root() {
+        query.insert("focus", path.to_string());
+    }
+
+    format!("{}{}", location.pathname.get(), query.to_query_string())
+}
+
+/// Link to the current page with `path` revealed in the explorer, leaves the focus.
+///
+/// Reads the location untracked, lists of many such links shouldn't rerender when one is followed.
+pub fn reveal_href(location: &Location, path: &NodePath) -> String {
+    let mut query = location.query.get_untracked();
+    query.remove("focus");
+    query.remove("view");
+    query.insert("path", path.to_string());
+
+    format!(
+        "{}{}",
+        location.pathname.get_untracked(),
+        query.to_query_string()
+    )
+}
+
+/// Sticky bar with the path of the selected node, every segment leads to that node.
+///
+/// Segments above the focused node leave the focus so the tree can be navigated back up.
+#[component]
+pub fn Breadcrumbs(
+    selected: Signal<Option<NodePath>>,
+    focus: Signal<Option<NodePath>>,
+    #[prop(into)] on_select: Callback<NodePath>,
+) -> impl IntoView {
+    let location = use_location();
+
+    move || {
+        let focus = focus.get();
+        let shown = selected.get().or_else(|| focus.clone()).unwrap_or_default();
+
+        let crumbs = shown
+            .ancestors()
+            .map(|prefix| {
+                let label = prefix
+                    .last()
+                    .map_or_else(|| "data.raw".to_string(), ToString::to_string);
+                let above_focus = focus
+                    .as_ref()
+                    .is_some_and(|f| prefix.contains(f) && prefix != *f);
+
+                if above_focus {
+                    view! { <A href=focus_href(&location, &prefix)>{label}</A> }.into_any()
+                } else {
+                    view! { <a on:click=move |_| on_select.run(prefix.clone())>{label}</a> }
+                        .into_any()
+                }
+            })
+            .collect_view();
+
+        let focus_link = (!shown.is_root() && focus.as_ref() != Some(&shown)).then(|| {
+            view! { <span class="action"><A href=focus_href(&location, &shown)>"focus"</A></span> }
+        });
+        let exit_link = focus.is_some().then(|| {
+            view! {
+                <span class="action">
+                    <A href=focus_href(&location, &NodePath::root())>"exit focus"</A>
+                </span>
+            }
+        });
+
+        view! {
+            <nav class="breadcrumbs">
+                {crumbs}
+                {focus_link}
+                {exit_link}
+            </nav>
+        }
+    }
+}
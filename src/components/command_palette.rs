@@ -0,0 +1,211 @@
+use std::sync::Arc;
+
+use leptos::{ev, html, prelude::*};
+use leptos_router::hooks::{use_location, use_navigate};
+
+use crate::util::{DedupValue, NodePath, TypeHelper, fuzzy_rank};
+
+/// How many matches the palette lists at once.
+const MAX_RESULTS: usize = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum PaletteEntry {
+    /// `data.raw.<type>.<name>` of the loaded dump.
+    Prototype { type_: Arc<str>, name: Arc<str> },
+    /// A prototype or type page of the API docs.
+    Docs { name: Arc<str>, link: String },
+}
+
+impl PaletteEntry {
+    fn name(&self) -> &str {
+        match self {
+            Self::Prototype { name, .. } | Self::Docs { name, .. } => name,
+        }
+    }
+
+    fn index(data: &DedupValue, doc: Option<&TypeHelper>) -> Vec<Self> {
+        let mut entries = Vec::new();
+
+        if let DedupValue::Object(types) = data {
+            for (type_, protos) in types.iter() {
+                let DedupValue::Object(protos) = protos else {
+                    continue;
+                };
+
+                entries.extend(protos.keys().map(|name| Self::Prototype {
+                    type_: type_.clone(),
+                    name: name.clone(),
+                }));
+            }
+        }
+
+        if let Some(doc) = doc {
+            entries.extend(
+                doc.doc_pages()
+                    .into_iter()
+                    .map(|(name, link)| Self::Docs { name, link }),
+            );
+        }
+
+        entries
+    }
+}
+
+/// Ctrl+K palette to jump to any prototype of the dump or open a docs page.
+#[component]
+pub fn CommandPalette(
+    data: DedupValue,
+    #[prop(optional_no_strip)] doc: Option<TypeHelper>,
+) -> impl IntoView {
+    let open = RwSignal::new(false);
+    let query = RwSignal::new(String::new());
+    let highlighted = RwSignal::new(0usize);
+    let input: NodeRef<html::Input> = NodeRef::new();
+
+    // only built once the palette is used, the dump can have a lot of prototypes
+    let entries = StoredValue::new(None::<Arc<[PaletteEntry]>>);
+    let results = Memo::new(move |_| {
+        if !open.get() {
+            return Vec::new();
+        }
+
+        let query = query.get();
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let entries = entries
+            .try_update_value(|entries| {
+                entries
+                    .get_or_insert_with(|| PaletteEntry::index(&data, doc.as_ref()).into())
+                    .clone()
+            })
+            .unwrap_or_default();
+
+        fuzzy_rank(&query, entries.iter(), |e| e.name())
+            .into_iter()
+            .take(MAX_RESULTS)
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+
+    let handle = window_event_listener(ev::keydown, move |ev| {
+        if ev.key() == "k" && (ev.ctrl_key() || ev.meta_key()) {
+            ev.prevent_default();
+            open.update(|o| *o = !*o);
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    Effect::new(move |_| {
+        if open.get()
+            && let Some(input) = input.get()
+        {
+            let _ = input.focus();
+        }
+    });
+
+    let close = move || {
+        open.set(false);
+        query.set(String::new());
+        highlighted.set(0);
+    };
+
+    let navigate = use_navigate();
+    let location = use_location();
+    let pick = move |entry: PaletteEntry| {
+        close();
+        match entry {
+            PaletteEntry::Prototype { type_, name } => {
+                let path = NodePath::root().child(type_).child(name);
+                let mut params = location.query.get_untracked();
+                params.remove("focus");
+                params.remove("path");
+                params.remove("view");
+                params.insert("path", path.to_string());
+
+                let href = format!(
+                    "{}{}",
+                    location.pathname.get_untracked(),
+                    params.to_query_string()
+                );
+                navigate(&href, Default::default());
+            }
+            PaletteEntry::Docs { link, .. } => {
+                let _ = window().open_with_url_and_target(&link, "_blank");
+            }
+        }
+    };
+
+    let on_keydown = {
+        let pick = pick.clone();
+        move |ev: ev::KeyboardEvent| {
+            let len = results.with(Vec::len);
+            match ev.key().as_str() {
+                "ArrowDown" if len > 0 => highlighted.update(|h| *h = (*h + 1) % len),
+                "ArrowUp" if len > 0 => highlighted.update(|h| *h = (*h + len - 1) % len),
+                "Enter" => {
+                    if let Some(entry) = results.with(|r| r.get(highlighted.get()).cloned()) {
+                        pick(entry);
+                    }
+                }
+                "Escape" => close(),
+                _ => return,
+            }
+
+            ev.prevent_default();
+        }
+    };
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="command-palette-backdrop" on:click=move |_| close()>
+                <div class="command-palette" on:click=|ev| ev.stop_propagation()>
+                    <input
+                        type="text"
+                        node_ref=input
+                        placeholder="jump to a prototype or docs page"
+                        aria-controls="command-palette-results"
+                        prop:value=query
+                        on:input=move |ev| {
+                            query.set(event_target_value(&ev));
+                            highlighted.set(0);
+                        }
+                        on:keydown=on_keydown.clone()
+                    />
+                    <ul id="command-palette-results" role="listbox">
+                        <For
+                            each=move || results.get().into_iter().enumerate()
+                            key=|(idx, e)| (*idx, e.clone())
+                            let((idx, entry))
+                        >
+                            {
+                                let pick = pick.clone();
+                                let (name, kind) = match &entry {
+                                    PaletteEntry::Prototype { type_, name } => {
+                                        (name.clone(), format!("data.raw.{type_}"))
+                                    }
+                                    PaletteEntry::Docs { name, .. } => (name.clone(), "docs".to_string()),
+                                };
+
+                                view! {
+                                    <li
+                                        role="option"
+                                        aria-selected=move || (highlighted.get() == idx).to_string()
+                                        class:highlighted=move || highlighted.get() == idx
+                                        on:mouseenter=move |_| highlighted.set(idx)
+                                        on:click=move |_| pick(entry.clone())
+                                    >
+                                        <span class="name">{name}</span>
+                                        <span class="kind">{kind}</span>
+                                    </li>
+                                }
+                            }
+                        </For>
+                    </ul>
+                    <p class="hint">"↑↓ to select, enter to open, esc to close"</p>
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,231 @@
+use leptos::prelude::*;
+use leptos_router::{
+    components::A,
+    hooks::{use_location, use_query_map},
+    location::Url,
+};
+
+use crate::{
+    app::{doc_at, load_api_docs},
+    components::LoadErrorView,
+    util::{
+        AlignedRow, Cell, MAX_COMPARE_ROWS, NodeSpec, TypeHelper, align, get_dump,
+        use_public_config,
+    },
+};
+
+/// Link to the comparison of `left` with `right`, the right side is picked on that page if missing.
+pub fn compare_href(left: &NodeSpec, right: Option<&NodeSpec>) -> String {
+    let left = Url::escape(&left.to_string());
+    match right {
+        Some(right) => format!("/compare?a={left}&b={}", Url::escape(&right.to_string())),
+        None => format!("/compare?a={left}"),
+    }
+}
+
+fn explorer_href(spec: &NodeSpec) -> String {
+    let variant = match &spec.version {
+        Some(version) => format!("{}/{version}", spec.variant),
+        None => spec.variant.clone(),
+    };
+    format!("/e/{variant}?path={}", Url::escape(&spec.path.to_string()))
+}
+
+/// Two nodes of the same or different variants side by side, given as `a` and `b` parameters.
+#[component]
+pub fn CompareView() -> impl IntoView {
+    let query = use_query_map();
+    let spec = move |side: &str| query.read().get(side).and_then(|s| NodeSpec::parse(&s));
+    let left = Memo::new(move |_| spec("a"));
+    let right = Memo::new(move |_| spec("b"));
+
+    let config = use_public_config();
+    // changing the path of a side shouldn't download its dump again
+    let load = move |spec: Memo<Option<NodeSpec>>| {
+        let source = Memo::new(move |_| spec.get().map(|s| (s.variant, s.version)));
+        LocalResource::new(move || {
+            let source = source.get();
+            async move {
+                match source {
+                    Some((variant, version)) => {
+                        Some(get_dump(config.await.resolver(), variant, version, None).await)
+                    }
+                    None => None,
+                }
+            }
+        })
+    };
+    let left_dump = load(left);
+    let right_dump = load(right);
+    let api_docs = LocalResource::new(load_api_docs);
+
+    let location = use_location();
+    let swap_href = move || {
+        let mut params = location.query.get();
+        let (a, b) = (params.get("a"), params.get("b"));
+        params.remove("a");
+        params.remove("b");
+        if let Some(b) = b {
+            params.insert("a", b);
+        }
+        if let Some(a) = a {
+            params.insert("b", a);
+        }
+        format!("/compare{}", params.to_query_string())
+    };
+
+    let only_differences = RwSignal::new(false);
+
+    view! {
+        <form class="compare-form" method="get" action="/compare">
+            <input
+                type="text"
+                name="a"
+                placeholder="base:data.raw.inserter.fast-inserter"
+                prop:value=move || query.read().get("a").unwrap_or_default()
+            />
+            <A href=swap_href>"⇄"</A>
+            <input
+                type="text"
+                name="b"
+                placeholder="base:data.raw.inserter.bulk-inserter"
+                prop:value=move || {
+                    // start from the left side, usually only the name changes
+                    query.with(|q| q.get("b").or_else(|| q.get("a")).unwrap_or_default())
+                }
+            />
+            <button type="submit">"compare"</button>
+        </form>
+        <label class="compare-filter">
+            <input
+                type="checkbox"
+                prop:checked=only_differences
+                on:change=move |ev| only_differences.set(event_target_checked(&ev))
+            />
+            "only show differences"
+        </label>
+        <Transition fallback=move || view! { <p>"Loading..."</p> }>
+            {move || Suspend::new(async move {
+                let (Some(l), Some(r)) = (left.get(), right.get()) else {
+                    return view! {
+                        <p>
+                            "Enter two nodes as " <code>"variant[/version]:path"</code>
+                            " to compare them."
+                        </p>
+                    }
+                    .into_any();
+                };
+
+                let (l_dump, r_dump) = match (left_dump.await, right_dump.await) {
+                    (Some(Ok(a)), Some(Ok(b))) => (a.data, b.data),
+                    (Some(Err(e)), _) => {
+                        return view! { <LoadErrorView error=e retry=move |()| left_dump.refetch() /> }
+                            .into_any();
+                    }
+                    (_, Some(Err(e))) => {
+                        return view! { <LoadErrorView error=e retry=move |()| right_dump.refetch() /> }
+                            .into_any();
+                    }
+                    _ => return ().into_any(),
+                };
+
+                let docs_url = config.await.docs_url;
+                let doc = api_docs.await.ok().map(|d| TypeHelper::new(d, &docs_url));
+
+                let missing = |spec: &NodeSpec| {
+                    view! {
+                        <p>"There is nothing at " <code>{spec.to_string()}</code> "."</p>
+                    }
+                    .into_any()
+                };
+                let Some(l_val) = l_dump.get_path(&l.path) else {
+                    return missing(&l);
+                };
+                let Some(r_val) = r_dump.get_path(&r.path) else {
+                    return missing(&r);
+                };
+
+                let (rows, complete) = align(
+                    l_val,
+                    r_val,
+                    doc_at(doc.clone(), &l_dump, &l.path),
+                    doc_at(doc, &r_dump, &r.path),
+                );
+
+                view! {
+                    <ComparisonTable left=l right=r rows=rows only_differences=only_differences />
+                    {(!complete).then(|| view! {
+                        <p class="truncated">"stopped after " {MAX_COMPARE_ROWS} " rows"</p>
+                    })}
+                }
+                .into_any()
+            })}
+        </Transition>
+    }
+}
+
+#[component]
+fn ComparisonTable(
+    left: NodeSpec,
+    right: NodeSpec,
+    rows: Vec<AlignedRow>,
+    only_differences: RwSignal<bool>,
+) -> impl IntoView {
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            let class = match (&row.left, &row.right) {
+                (None, _) => "differs missing-left",
+                (_, None) => "differs missing-right",
+                _ if row.differs => "differs",
+                _ => "",
+            };
+            let differs = row.differs;
+            let key = if row.key.is_empty() {
+                "(node)".to_string()
+            } else {
+                row.key.to_string()
+            };
+
+            view! {
+                <tr class=class hidden=move || only_differences.get() && !differs>
+                    <td class="key" style:padding-left=format!("{}.5rem", row.depth)>
+                        {key}
+                    </td>
+                    {cells(row.left)}
+                    {cells(row.right)}
+                </tr>
+            }
+        })
+        .collect_view();
+
+    let header = |spec: &NodeSpec| {
+        view! {
+            <th colspan="2">
+                <A href=explorer_href(spec)>{spec.to_string()}</A>
+            </th>
+        }
+    };
+
+    view! {
+        <table class="comparison">
+            <thead>
+                <tr>
+                    <th>"key"</th>
+                    {header(&left)}
+                    {header(&right)}
+                </tr>
+            </thead>
+            <tbody>{rows}</tbody>
+        </table>
+    }
+}
+
+fn cells(cell: Option<Cell>) -> impl IntoView {
+    let (value, type_) =
+        cell.map_or_else(Default::default, |c| (c.value, c.type_.unwrap_or_default()));
+    view! {
+        <td class="value">{value}</td>
+        <td class="type">{type_}</td>
+    }
+}
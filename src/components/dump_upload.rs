@@ -0,0 +1,139 @@
+use leptos::{ev, html, prelude::*, task::spawn_local};
+use leptos_icons::Icon;
+use leptos_router::hooks::use_navigate;
+
+use crate::util::{DedupValue, LoadError};
+
+/// A dump loaded from the user's machine, it never leaves the browser.
+#[derive(Debug, Clone)]
+pub struct UploadedDump {
+    pub name: String,
+    pub data: DedupValue,
+}
+
+/// Holds the dump opened through [`DumpUpload`], provided by the app.
+#[derive(Debug, Clone, Copy)]
+pub struct UploadedDumpStore(pub RwSignal<Option<UploadedDump>>);
+
+impl UploadedDumpStore {
+    pub fn new() -> Self {
+        Self(RwSignal::new(None))
+    }
+}
+
+impl Default for UploadedDumpStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum UploadState {
+    Idle,
+    Parsing(String),
+    Failed(String),
+}
+
+#[component]
+pub fn DumpUpload() -> impl IntoView {
+    let store = expect_context::<UploadedDumpStore>();
+    let state = RwSignal::new(UploadState::Idle);
+    let dragging = RwSignal::new(false);
+    let pasted: NodeRef<html::Textarea> = NodeRef::new();
+    let navigate = use_navigate();
+
+    let open = move |name: String, text: String| {
+        state.set(UploadState::Parsing(name.clone()));
+
+        match serde_json::from_str::<DedupValue>(&text) {
+            Ok(data) => {
+                store.0.set(Some(UploadedDump { name, data }));
+                state.set(UploadState::Idle);
+                navigate("/upload", Default::default());
+            }
+            Err(e) => state.set(UploadState::Failed(format!(
+                "{name}: {}",
+                LoadError::from(e)
+            ))),
+        }
+    };
+
+    let open_file = {
+        let open = open.clone();
+        move |file: web_sys::File| {
+            let name = file.name();
+            state.set(UploadState::Parsing(name.clone()));
+
+            let open = open.clone();
+            spawn_local(async move {
+                let text = wasm_bindgen_futures::JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|t| t.as_string());
+
+                match text {
+                    Some(text) => open(name, text),
+                    None => state.set(UploadState::Failed(format!("{name}: could not read file"))),
+                }
+            });
+        }
+    };
+
+    let on_drop = {
+        let open_file = open_file.clone();
+        move |ev: ev::DragEvent| {
+            ev.prevent_default();
+            dragging.set(false);
+
+            if let Some(file) = ev
+                .data_transfer()
+                .and_then(|dt| dt.files())
+                .and_then(|files| files.get(0))
+            {
+                open_file(file);
+            }
+        }
+    };
+
+    let on_pick = move |ev: ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        if let Some(file) = input.files().and_then(|files| files.get(0)) {
+            open_file(file);
+        }
+    };
+
+    let on_paste = move |_| {
+        let text = pasted.get().expect("<textarea> should be mounted").value();
+        if !text.trim().is_empty() {
+            open("pasted dump".to_string(), text);
+        }
+    };
+
+    view! {
+        <div
+            class="dump-upload"
+            class:dragging=dragging
+            on:dragover=move |ev: ev::DragEvent| {
+                ev.prevent_default();
+                dragging.set(true);
+            }
+            on:dragleave=move |_| dragging.set(false)
+            on:drop=on_drop
+        >
+            <label>
+                <Icon icon={icondata::FiUpload}/>
+                " Drop a "<code>"data-raw-dump.json"</code>" here or "
+                <span class="pick">"pick a file"</span>
+                <input type="file" accept=".json,application/json" on:change=on_pick/>
+            </label>
+            <textarea node_ref=pasted placeholder="...or paste the dump here"/>
+            <button on:click=on_paste>"Open pasted dump"</button>
+            <p class="hint">"The dump is only parsed in your browser, nothing gets uploaded."</p>
+            {move || match state.get() {
+                UploadState::Idle => ().into_any(),
+                UploadState::Parsing(name) => view! { <p>"Parsing " {name} "..."</p> }.into_any(),
+                UploadState::Failed(e) => view! { <p class="error">{e}</p> }.into_any(),
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,59 @@
+use leptos::prelude::*;
+
+use crate::util::{DedupValue, ExpansionStore, MAX_EXPANDED_ROWS, NodePath};
+
+/// Bulk expand / collapse actions for the subtree at `path`.
+///
+/// Used per node and, with `toolbar` set, for the whole dump above the explorer.
+#[component]
+pub fn ExpandControls(
+    path: NodePath,
+    val: DedupValue,
+    #[prop(optional)] toolbar: bool,
+) -> impl IntoView {
+    let Some(expansion) = use_context::<ExpansionStore>() else {
+        return ().into_any();
+    };
+
+    let depth = RwSignal::new(2usize);
+    let truncated = RwSignal::new(false);
+    let path = StoredValue::new(path);
+    let val = StoredValue::new(val);
+
+    let expand = move |depth: usize| {
+        let complete = expansion.expand(&path.read_value(), &val.read_value(), depth);
+        truncated.set(!complete);
+    };
+
+    let (expand_label, collapse_label) = if toolbar {
+        ("expand all", "collapse all")
+    } else {
+        ("expand all children", "collapse all below")
+    };
+
+    view! {
+        <span class="expand-controls" class:toolbar=toolbar>
+            <button on:click=move |_| expand(usize::MAX)>{expand_label}</button>
+            <button on:click=move |_| expand(depth.get())>"expand to depth"</button>
+            <input
+                type="number"
+                min="1"
+                max="99"
+                prop:value=move || depth.get().to_string()
+                on:change=move |ev| {
+                    if let Ok(d) = event_target_value(&ev).parse::<usize>() {
+                        depth.set(d.max(1));
+                    }
+                }
+            />
+            <button on:click=move |_| {
+                truncated.set(false);
+                expansion.collapse_below(&path.read_value());
+            }>{collapse_label}</button>
+            <Show when=move || truncated.get()>
+                <span class="truncated">"stopped after " {MAX_EXPANDED_ROWS} " rows"</span>
+            </Show>
+        </span>
+    }
+    .into_any()
+}
@@ -0,0 +1,71 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+use crate::{
+    components::get_available_mods,
+    util::{LoadError, suggestions},
+};
+
+#[component]
+pub fn LoadErrorView(error: LoadError, #[prop(into)] retry: Callback<()>) -> impl IntoView {
+    let content = match error {
+        LoadError::NotFound(name) => view! {
+            <p>"Mod " <code>{name.clone()}</code> " was not found."</p>
+            <ModSuggestions query=name />
+        }
+        .into_any(),
+        LoadError::Parse {
+            line,
+            column,
+            message,
+        } => view! {
+            <p>"The dump is malformed at line " {line} ", column " {column} ":"</p>
+            <pre>{message}</pre>
+        }
+        .into_any(),
+        e if e.is_transient() => view! {
+            <p>{e.to_string()}</p>
+            <button on:click=move |_| retry.run(())>"Retry"</button>
+        }
+        .into_any(),
+        e => view! { <p>{e.to_string()}</p> }.into_any(),
+    };
+
+    view! {
+        <div class="load-error">
+            {content}
+        </div>
+    }
+}
+
+#[component]
+fn ModSuggestions(query: String) -> impl IntoView {
+    let mods = Resource::new(|| (), async |_| get_available_mods().await);
+
+    view! {
+        <Suspense>
+            {move || {
+                let query = query.clone();
+                Suspend::new(async move {
+                    let Ok(mods) = mods.await else {
+                        return ().into_any();
+                    };
+
+                    let similar = suggestions(&query, mods.iter().map(|m| m.name.as_str()), 5);
+                    if similar.is_empty() {
+                        return ().into_any();
+                    }
+
+                    view! {
+                        <p>"Did you mean: "
+                            {similar.into_iter().map(|name| view! {
+                                <A href=format!("/e/{name}")>{name.to_string()}</A>
+                            }).collect_view()}
+                        </p>
+                    }
+                    .into_any()
+                })
+            }}
+        </Suspense>
+    }
+}
@@ -0,0 +1,45 @@
+use leptos::prelude::*;
+
+use super::format_size;
+use crate::util::DumpProgress;
+
+/// Loading message with the download and parse progress of a dump.
+#[component]
+pub fn LoadProgress(#[prop(into)] progress: Signal<DumpProgress>) -> impl IntoView {
+    move || {
+        let DumpProgress {
+            downloaded,
+            total,
+            parsed,
+        } = progress.get();
+        // compressed responses announce less than they end up delivering
+        let total = total.filter(|total| *total >= downloaded && *total > 0);
+
+        let (label, value, max) = match (parsed, total) {
+            (Some(parsed), _) => (format!("Parsing... {parsed}%"), Some(parsed.into()), 100),
+            (None, _) if downloaded == 0 => ("Loading...".to_string(), None, 100),
+            (None, Some(total)) => (
+                format!(
+                    "Downloading... {} of {}",
+                    format_size(downloaded),
+                    format_size(total)
+                ),
+                Some(downloaded),
+                total,
+            ),
+            (None, None) => (
+                format!("Downloading... {}", format_size(downloaded)),
+                None,
+                100,
+            ),
+        };
+
+        view! {
+            <div class="load-progress">
+                <p>{label}</p>
+                // without a value the bar shows an indeterminate state
+                <progress max=max value=value></progress>
+            </div>
+        }
+    }
+}
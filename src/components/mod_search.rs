@@ -0,0 +1,214 @@
+use leptos::{
+    ev::{KeyboardEvent, SubmitEvent},
+    prelude::*,
+};
+use leptos_icons::Icon;
+use leptos_router::{
+    components::Redirect,
+    hooks::{use_navigate, use_params, use_query_map},
+};
+
+use crate::{
+    app::VariantParams,
+    components::get_available_mods,
+    util::{LoadError, ModVersions, fuzzy_rank, use_history, use_public_config},
+};
+
+/// How many matches the dropdown shows at once.
+const MAX_RESULTS: usize = 20;
+
+/// Search box for all available mods with a ranked dropdown of matches.
+///
+/// Without JS the form falls back to a plain GET to `/search` which redirects to the best match.
+#[component]
+pub fn ModSearch(mods: Resource<Result<Box<[ModVersions]>, LoadError>>) -> impl IntoView {
+    let params = use_params::<VariantParams>();
+    let config = use_public_config();
+    let navigate = use_navigate();
+    let history = use_history().get();
+
+    // featured mods already have their own link, only prefill the search for other mods
+    let current = move || {
+        params
+            .read()
+            .as_ref()
+            .ok()
+            .and_then(|p| p.variant.clone())
+            .filter(|v| !config.get().is_some_and(|c| c.is_featured(v)))
+            .unwrap_or_default()
+    };
+
+    let query = RwSignal::new(String::new());
+    let open = RwSignal::new(false);
+    let highlighted = RwSignal::new(0usize);
+
+    Effect::new(move |_| query.set(current()));
+
+    let results = Memo::new(move |_| {
+        let query = query.get();
+        mods.with(|mods| {
+            let Some(Ok(mods)) = mods else {
+                return Vec::new();
+            };
+
+            fuzzy_rank(&query, mods.iter(), |m| m.name.as_str())
+                .into_iter()
+                .take(MAX_RESULTS)
+                .cloned()
+                .map(|mut m| {
+                    // the server only counts local dumps, the others are known once opened
+                    if m.prototypes.is_none() {
+                        m.prototypes = history.with(|h| h.prototypes.get(&m.name).copied());
+                    }
+                    m
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let go = move |name: String| {
+        open.set(false);
+        query.set(name.clone());
+        navigate(&format!("/e/{name}"), Default::default());
+    };
+
+    let on_submit = {
+        let go = go.clone();
+        move |ev: SubmitEvent| {
+            ev.prevent_default();
+
+            let picked = results.with(|r| r.get(highlighted.get()).map(|m| m.name.clone()));
+            let name = picked.unwrap_or_else(|| query.get().trim().to_string());
+            if !name.is_empty() {
+                go(name);
+            }
+        }
+    };
+
+    let on_keydown = move |ev: KeyboardEvent| {
+        let len = results.with(Vec::len);
+        match ev.key().as_str() {
+            "ArrowDown" => {
+                ev.prevent_default();
+                open.set(true);
+                highlighted.update(|h| *h = if len == 0 { 0 } else { (*h + 1) % len });
+            }
+            "ArrowUp" => {
+                ev.prevent_default();
+                open.set(true);
+                highlighted.update(|h| *h = if len == 0 { 0 } else { (*h + len - 1) % len });
+            }
+            "Escape" => open.set(false),
+            _ => {}
+        }
+    };
+
+    let active = move || {
+        (open.get() && highlighted.get() < results.with(Vec::len))
+            .then(|| format!("mod-search-{}", highlighted.get()))
+    };
+
+    view! {
+        <form class="mod-search" method="get" action="/search" on:submit=on_submit>
+            <input
+                type="text"
+                id="mod-search"
+                name="mod"
+                autocomplete="off"
+                placeholder="search for other mods"
+                role="combobox"
+                aria-controls="mod-search-results"
+                aria-autocomplete="list"
+                aria-expanded=move || open.get().to_string()
+                aria-activedescendant=active
+                value=current
+                prop:value=query
+                on:input=move |ev| {
+                    query.set(event_target_value(&ev));
+                    highlighted.set(0);
+                    open.set(true);
+                }
+                on:keydown=on_keydown
+                on:focus=move |_| open.set(true)
+                on:blur=move |_| open.set(false)
+            />
+            <button type="submit">
+                <Icon icon={icondata::FiSearch}/>
+            </button>
+            <Show when=move || open.get() && !results.with(Vec::is_empty)>
+                <ul id="mod-search-results" role="listbox">
+                    <For
+                        each=move || results.get().into_iter().enumerate()
+                        key=|(idx, m)| (*idx, m.name.clone())
+                        let((idx, m))
+                    >
+                        {
+                            let go = go.clone();
+                            let name = m.name.clone();
+                            let versions = match m.versions.len() {
+                                1 => m.versions[0].clone(),
+                                n => format!("{}, {n} versions", m.latest().unwrap_or_default()),
+                            };
+
+                            view! {
+                                <li
+                                    id=format!("mod-search-{idx}")
+                                    role="option"
+                                    aria-selected=move || (highlighted.get() == idx).to_string()
+                                    class:highlighted=move || highlighted.get() == idx
+                                    on:mouseenter=move |_| highlighted.set(idx)
+                                    // mousedown fires before the input loses focus and closes the list
+                                    on:mousedown=move |ev| {
+                                        ev.prevent_default();
+                                        go(name.clone());
+                                    }
+                                >
+                                    <span class="name">{m.name.clone()}</span>
+                                    <span class="versions">{versions}</span>
+                                    {m.prototypes.map(|n| view! {
+                                        <span class="prototypes">{n} " prototypes"</span>
+                                    })}
+                                </li>
+                            }
+                        }
+                    </For>
+                </ul>
+            </Show>
+        </form>
+    }
+}
+
+/// Target of the [`ModSearch`] form before hydration, redirects to the best matching mod.
+#[component]
+pub fn SearchRedirect() -> impl IntoView {
+    let query = use_query_map();
+    let target = Resource::new(
+        move || query.read().get("mod").unwrap_or_default(),
+        async |query| {
+            let query = query.trim().to_string();
+            if query.is_empty() {
+                return "/".to_string();
+            }
+
+            let name = get_available_mods()
+                .await
+                .ok()
+                .and_then(|mods| {
+                    fuzzy_rank(&query, mods.iter(), |m| m.name.as_str())
+                        .first()
+                        .map(|m| m.name.clone())
+                })
+                .unwrap_or(query);
+
+            format!("/e/{name}")
+        },
+    );
+
+    view! {
+        <Suspense fallback=|| "searching...">
+            {move || Suspend::new(async move {
+                view! { <Redirect path=target.await /> }
+            })}
+        </Suspense>
+    }
+}
@@ -1,125 +1,133 @@
-use leptos::{ev::SubmitEvent, html, prelude::*};
-use leptos_icons::Icon;
+use leptos::prelude::*;
 use leptos_router::{
     components::A,
     hooks::{use_navigate, use_params},
 };
 
-use crate::app::VariantParams;
-
-const WUBE_MODS: [&str; 5] = ["base", "space-age", "quality", "recycler", "elevated-rails"];
+use crate::{
+    app::VariantParams,
+    components::{ModSearch, RecentMods},
+    util::{LoadError, ModVersions, use_public_config},
+};
 
 #[component]
 pub fn ModSelector() -> impl IntoView {
-    let params = use_params::<VariantParams>();
-    let variant = move || params.read().as_ref().ok().and_then(|p| p.variant.clone());
-
+    let config = use_public_config();
     let mods = Resource::new(|| (), async |_| get_available_mods().await);
 
-    let other_mod: NodeRef<html::Input> = NodeRef::new();
-    let on_submit = move |ev: SubmitEvent| {
-        ev.prevent_default();
-
-        let val = other_mod.get().expect("<input> should be mounted").value();
-        if !val.is_empty() {
-            let navigate = use_navigate();
-            navigate(&format!("/e/{}", val), Default::default());
-        }
-    };
-
     view! {
         <div class="mod-select">
-            {
-                WUBE_MODS.iter().map(|m| {
-                    let m = m.to_string();
-                    view! {
-                        <A href={format!("/e/{}", m.clone())}>
-                            {m.clone()}
-                        </A>
-                    }
-                }).collect_view()
-            }
-
-            <form on:submit=on_submit>
-                <input type="text" list="mods" node_ref=other_mod placeholder="search for other mods" value=move|| {
-                    if let Some(v) = variant() && !WUBE_MODS.contains(&v.as_str()) {
-                        v
-                    } else {
-                        "".to_string()
-                    }
-                }/>
-                <datalist id="mods">
-                    <Suspense>
-                        {move || Suspend::new(async move {
-                            let Ok(mods) = mods.await else {
-                                return ().into_any();
-                            };
-
-                            mods.iter().filter_map(|(name, version)| {
-                                if WUBE_MODS.contains(&name.as_str()) {
-                                    return None;
-                                }
-
-                                let res = view! {
-                                    <option value={name.clone()}>{name.clone()} " (" {version.clone()} ")"</option>
-                                };
-
-                                Some(res)
-                            }).collect_view().into_any()
-                        })}
-                    </Suspense>
-                </datalist>
-                <button type="submit">
-                    <Icon icon={icondata::FiSearch}/>
-                </button>
-            </form>
+            <Suspense>
+                {move || Suspend::new(async move {
+                    config.await.featured_mods.into_iter().map(|m| {
+                        view! {
+                            <A href={format!("/e/{}", m.clone())}>
+                                {m.clone()}
+                            </A>
+                        }
+                    }).collect_view()
+                })}
+            </Suspense>
+
+            <ModSearch mods=mods />
+            <VersionSelector mods=mods />
         </div>
+        <RecentMods />
     }
 }
 
-#[cfg(feature = "ssr")]
-#[derive(serde::Serialize, serde::Deserialize)]
-struct AvailableMods {
-    #[serde(rename = "processed")]
-    raw: std::collections::BTreeSet<String>,
-}
+#[component]
+fn VersionSelector(mods: Resource<Result<Box<[ModVersions]>, LoadError>>) -> impl IntoView {
+    let params = use_params::<VariantParams>();
+    let selected = move || {
+        params
+            .read()
+            .as_ref()
+            .ok()
+            .and_then(|p| Some((p.variant.clone()?, p.version.clone())))
+    };
 
-#[cfg(feature = "ssr")]
-impl AvailableMods {
-    fn build_list(self) -> Box<[(String, String)]> {
-        let mut split = self
-            .raw
-            .iter()
-            .filter_map(|r| {
-                let mut parts = r.split('_').collect::<Vec<_>>();
-                if parts.len() < 2 {
-                    return None;
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                let Some((name, version)) = selected() else {
+                    return ().into_any();
+                };
+
+                let Some(versions) = mods
+                    .await
+                    .ok()
+                    .and_then(|mods| mods.iter().find(|m| m.name == name).cloned())
+                else {
+                    return ().into_any();
+                };
+
+                let current = version.clone().or_else(|| versions.latest().map(String::from));
+                let compare = current.as_ref().and_then(|current| {
+                    let previous = versions.previous(current)?;
+                    Some(format!("/e/{name}/{current}?base={previous}"))
+                });
+
+                let on_change = move |ev: leptos::ev::Event| {
+                    let version = event_target_value(&ev);
+                    let navigate = use_navigate();
+                    navigate(&format!("/e/{name}/{version}"), Default::default());
+                };
+
+                view! {
+                    <select class="version" on:change=on_change>
+                        {versions.versions.iter().enumerate().map(|(idx, v)| {
+                            let label = if idx == 0 { format!("{v} (latest)") } else { v.clone() };
+                            let is_selected = version.as_ref().map_or(idx == 0, |s| s == v);
+                            view! {
+                                <option value=v.clone() selected=is_selected>{label}</option>
+                            }
+                        }).collect_view()}
+                    </select>
+                    {compare.map(|href| view! {
+                        <A href=href>"compare with previous version"</A>
+                    })}
                 }
+                .into_any()
+            })}
+        </Suspense>
+    }
+}
+
+#[server]
+pub async fn get_available_mods() -> Result<Box<[ModVersions]>, LoadError> {
+    use crate::util::{DumpSource, LOCAL_VERSION, LocalDumps, PublicConfig, resolver_variants};
 
-                let version = parts.pop()?.to_string();
-                let name = parts.join("_");
+    let config = use_context::<PublicConfig>().unwrap_or_default();
+    let local = match use_context::<LocalDumps>() {
+        Some(local) => local.variants().await?,
+        None => Vec::new(),
+    };
 
-                Some((name, version))
-            })
-            .collect::<Vec<_>>();
+    // the resolver being unreachable is fine as long as there are local dumps
+    let mut mods = match resolver_variants().await {
+        Ok(mods) => mods,
+        Err(_) if !local.is_empty() => Vec::new(),
+        Err(e) => return Err(e),
+    };
 
-        split.sort_by(|(a, _), (b, _)| {
-            let a_wube = WUBE_MODS.iter().position(|&m| m == a).unwrap_or(usize::MAX);
-            let b_wube = WUBE_MODS.iter().position(|&m| m == b).unwrap_or(usize::MAX);
+    mods.extend(local);
 
-            match a_wube.cmp(&b_wube) {
-                std::cmp::Ordering::Equal => a.cmp(b),
-                other => other,
+    let mut mods = ModVersions::group(mods);
+    if let Some(local) = use_context::<LocalDumps>() {
+        for m in &mut mods {
+            if m.latest() == Some(LOCAL_VERSION) {
+                m.prototypes = local.prototype_count(&m.name).await;
             }
-        });
-        split.into_boxed_slice()
+        }
     }
-}
 
-#[server]
-pub async fn get_available_mods() -> Result<Box<[(String, String)]>, ServerFnError> {
-    crate::util::fetch_from_resolver::<AvailableMods>("stats")
-        .await
-        .map(AvailableMods::build_list)
-        .map_err(ServerFnError::ServerError)
+    mods.sort_by(|a, b| {
+        config
+            .featured_rank(&a.name)
+            .cmp(&config.featured_rank(&b.name))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(mods.into_boxed_slice())
 }
@@ -0,0 +1,224 @@
+use leptos::{prelude::*, task::spawn_local};
+use leptos_router::{components::A, hooks::use_location, location::Location};
+
+use crate::{
+    components::reveal_href,
+    util::{
+        DedupValue, DocsCoverage, DumpOverview, NodePath, TypeHelper, find_duplicates, preview,
+    },
+};
+
+/// Most prototypes listed per type, the remaining ones are only counted.
+const MAX_LISTED_PROTOTYPES: usize = 100;
+
+/// How many of the duplicated subtrees saving the most are listed.
+const MAX_DUPLICATES: usize = 50;
+
+/// Link to the current page switching the explorer to `view`, either `tree` or `overview`.
+pub fn view_href(location: &Location, view: &str) -> String {
+    let mut query = location.query.get();
+    query.insert("view", view.to_string());
+
+    format!("{}{}", location.pathname.get(), query.to_query_string())
+}
+
+/// Counts, sizes and docs coverage of a whole dump.
+#[component]
+pub fn DumpOverviewView(
+    data: DedupValue,
+    #[prop(optional_no_strip)] doc: Option<TypeHelper>,
+) -> impl IntoView {
+    let location = use_location();
+    let overview = DumpOverview::new(&data);
+    let duplicates = view! { <Duplicates data=data.clone() /> };
+
+    // typing every key walks the whole dump along the docs, so it goes one prototype type
+    // at a time and lets the browser paint and handle input in between
+    let coverage = RwSignal::new(None::<DocsCoverage>);
+    if let Some(doc) = doc.clone()
+        && let DedupValue::Object(types) = &data
+    {
+        let types = types.clone();
+        spawn_local(async move {
+            let mut counted = DocsCoverage::default();
+            for (name, protos) in types.iter() {
+                yield_to_browser().await;
+                if coverage.is_disposed() {
+                    return;
+                }
+                counted.add(name, protos, &doc);
+            }
+            coverage.try_set(Some(counted));
+        });
+    }
+
+    let docs = match &doc {
+        Some(doc) => view! {
+            <dt>"docs version"</dt>
+            <dd>{doc.docs_version()}</dd>
+            <dt>"docs coverage"</dt>
+            <dd>
+                {move || match coverage.get() {
+                    Some(coverage) => format!(
+                        "{:.1}% of {} keys have a known type",
+                        coverage.percent(),
+                        coverage.total
+                    ),
+                    None => "checking...".to_string(),
+                }}
+            </dd>
+        }
+        .into_any(),
+        None => view! {
+            <dt>"docs"</dt>
+            <dd>"unavailable"</dd>
+        }
+        .into_any(),
+    };
+
+    let largest = overview.types.first().map_or(1, |t| t.size).max(1);
+    let types = overview
+        .types
+        .iter()
+        .map(|t| {
+            let type_path = NodePath::root().child(t.name.clone());
+            #[allow(clippy::cast_precision_loss)]
+            let share = t.size as f64 / largest as f64 * 100.0;
+
+            let listed = t
+                .prototypes
+                .iter()
+                .take(MAX_LISTED_PROTOTYPES)
+                .map(|(name, size)| {
+                    let href = reveal_href(&location, &type_path.child(name.clone()));
+                    view! {
+                        <li>
+                            <A href=href>{name.to_string()}</A>
+                            <span class="size">{format_size(*size)}</span>
+                        </li>
+                    }
+                })
+                .collect_view();
+            let more = t.prototypes.len().saturating_sub(MAX_LISTED_PROTOTYPES);
+
+            view! {
+                <details>
+                    <summary>
+                        <A href=reveal_href(&location, &type_path)>{t.name.to_string()}</A>
+                        <span class="count">{t.prototypes.len()}</span>
+                        <span class="size">{format_size(t.size)}</span>
+                        <span class="share">
+                            <span style:width=format!("{share}%")></span>
+                        </span>
+                    </summary>
+                    <ol>
+                        {listed}
+                        {(more > 0).then(|| view! { <li class="more">"and " {more} " more"</li> })}
+                    </ol>
+                </details>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <section class="dump-overview">
+            <dl>
+                <dt>"prototypes"</dt>
+                <dd>{overview.prototypes} " in " {overview.types.len()} " types"</dd>
+                <dt>"size"</dt>
+                <dd>"about " {format_size(overview.size)} " of JSON"</dd>
+                <dt>"distinct strings"</dt>
+                <dd>{overview.distinct_strings}</dd>
+                {docs}
+            </dl>
+            <div class="overview-types">
+                <div class="overview-header">
+                    <span>"type"</span>
+                    <span class="count">"prototypes"</span>
+                    <span class="size">"size"</span>
+                </div>
+                {types}
+            </div>
+            {duplicates}
+        </section>
+    }
+}
+
+/// The largest subtrees shared between several places of the dump, only searched on request.
+#[component]
+fn Duplicates(data: DedupValue) -> impl IntoView {
+    let location = use_location();
+    let shown = RwSignal::new(false);
+    let data = StoredValue::new(data);
+
+    let list = move || {
+        if !shown.get() {
+            return None;
+        }
+
+        let rows = data
+            .with_value(|d| find_duplicates(d, MAX_DUPLICATES))
+            .into_iter()
+            .map(|dup| {
+                let locations = dup
+                    .locations
+                    .iter()
+                    .map(|path| {
+                        view! {
+                            <li><A href=reveal_href(&location, path)>{path.to_string()}</A></li>
+                        }
+                    })
+                    .collect_view();
+                let more = dup.count - dup.locations.len();
+
+                view! {
+                    <details>
+                        <summary>
+                            <span class="preview">{preview(&dup.value).unwrap_or_default()}</span>
+                            <span class="count">{dup.count} "×"</span>
+                            <span class="size">{format_size(dup.size)}</span>
+                            <span class="size">{format_size(dup.saved())} " saved"</span>
+                        </summary>
+                        <ol>
+                            {locations}
+                            {(more > 0).then(|| view! { <li class="more">"and " {more} " more"</li> })}
+                        </ol>
+                    </details>
+                }
+            })
+            .collect_view();
+
+        Some(rows)
+    };
+
+    view! {
+        <div class="overview-duplicates">
+            <h3>"Duplicated subtrees"</h3>
+            <p>
+                "Equal subtrees like sound definitions or sprites are only stored once, "
+                "these are the ones shared the most."
+            </p>
+            <Show when=move || !shown.get()>
+                <button on:click=move |_| shown.set(true)>"find duplicates"</button>
+            </Show>
+            {list}
+        </div>
+    }
+}
+
+/// Waits for the next task so the browser can render and handle events.
+async fn yield_to_browser() {
+    let next = js_sys::Promise::new(&mut |resolve, _| {
+        let _ = window().set_timeout_with_callback(&resolve);
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(next).await;
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -0,0 +1,275 @@
+use leptos::{ev::SubmitEvent, prelude::*};
+use leptos_router::{
+    components::A,
+    hooks::{use_location, use_navigate, use_query_map},
+    location::Location,
+};
+
+use crate::{
+    components::reveal_href,
+    util::{DedupValue, MAX_QUERY_RESULTS, PropertyStats, Query, QueryMatch},
+};
+
+/// Most outliers listed below the histogram.
+const MAX_OUTLIERS: usize = 50;
+
+/// Most distinct values listed in the frequency table, the rarest ones are only counted.
+const MAX_VALUES: usize = 100;
+
+/// Link to the current page showing the statistics of `pattern`, `None` closes them.
+pub fn stats_href(location: &Location, pattern: Option<&str>) -> String {
+    let mut query = location.query.get_untracked();
+    query.remove("stats");
+    if let Some(pattern) = pattern {
+        query.insert("stats", pattern.to_string());
+    }
+
+    format!(
+        "{}{}",
+        location.pathname.get_untracked(),
+        query.to_query_string()
+    )
+}
+
+/// Statistics of a property across prototypes, the pattern is kept in the `stats` parameter.
+#[component]
+pub fn PropertyStatsPanel(data: DedupValue) -> impl IntoView {
+    let query = use_query_map();
+    let location = use_location();
+    let navigate = use_navigate();
+
+    let current = Memo::new(move |_| query.read().get("stats"));
+    let input = RwSignal::new(String::new());
+    Effect::new(move |_| input.set(current.get().unwrap_or_default()));
+
+    let on_submit = {
+        let location = location.clone();
+        let navigate = navigate.clone();
+        move |ev: SubmitEvent| {
+            ev.prevent_default();
+            let pattern = input.get_untracked();
+            navigate(
+                &stats_href(&location, Some(pattern.trim())),
+                Default::default(),
+            );
+        }
+    };
+    let close = {
+        let location = location.clone();
+        move |_: leptos::ev::MouseEvent| navigate(&stats_href(&location, None), Default::default())
+    };
+
+    let data = StoredValue::new(data);
+    let stats = move || {
+        let pattern = current.get()?;
+        let stats = Query::parse(&pattern)
+            .map(|q| data.with_value(|d| PropertyStats::collect(&q, d)))
+            .map_err(|e| e.to_string());
+        Some(stats)
+    };
+
+    let location = StoredValue::new(location);
+    let body = move || {
+        let stats = match stats()? {
+            Ok(stats) => stats,
+            Err(e) => return Some(view! { <p class="stats-error">{e}</p> }.into_any()),
+        };
+        if stats.matches.is_empty() {
+            return Some(view! { <p>"No prototype has this property."</p> }.into_any());
+        }
+
+        // matches of the clicked bar / value
+        let picked = RwSignal::new(None::<(String, Vec<usize>)>);
+        let stats = StoredValue::new(stats);
+        let link = move |idx: usize| {
+            stats.with_value(|s| {
+                let m = &s.matches[idx];
+                view! {
+                    <li>
+                        <A href=location.with_value(|l| reveal_href(l, &m.path))>{m.path.to_string()}</A>
+                        <span class="value">{value_label(m)}</span>
+                    </li>
+                }
+            })
+        };
+
+        let (count, other) = stats.with_value(|s| (s.matches.len(), s.other));
+        let summary = view! {
+            <p class="stats-summary">
+                {count} " matches"
+                {(other > 0).then(|| format!(", {other} of them not a number, string or boolean"))}
+            </p>
+        };
+
+        let numbers = stats.with_value(|s| s.numbers.clone()).map(|n| {
+            let highest = n
+                .bins
+                .iter()
+                .map(|b| b.members.len())
+                .max()
+                .unwrap_or(1)
+                .max(1);
+            let bars = n
+                .bins
+                .into_iter()
+                .map(|bin| {
+                    let label = format!(
+                        "{} – {}: {}",
+                        fmt(bin.start),
+                        fmt(bin.end),
+                        bin.members.len()
+                    );
+                    #[allow(clippy::cast_precision_loss)]
+                    let height = bin.members.len() as f64 / highest as f64 * 100.0;
+                    let empty = bin.members.is_empty();
+                    let pick = (label.clone(), bin.members);
+
+                    view! {
+                        <button
+                            class="bar"
+                            title=label
+                            disabled=empty
+                            style:height=format!("{height}%")
+                            on:click=move |_| picked.set(Some(pick.clone()))
+                        ></button>
+                    }
+                })
+                .collect_view();
+
+            let outliers = (!n.outliers.is_empty()).then(|| {
+                let shown = n
+                    .outliers
+                    .iter()
+                    .take(MAX_OUTLIERS)
+                    .map(|&idx| link(idx))
+                    .collect_view();
+                view! {
+                    <h4>"outliers (" {n.outliers.len()} ")"</h4>
+                    <ul class="stats-matches">{shown}</ul>
+                }
+            });
+
+            view! {
+                <table class="stats-numbers">
+                    <tr><th>"count"</th><td>{n.count}</td></tr>
+                    <tr><th>"min"</th><td>{fmt(n.min)}</td></tr>
+                    <tr><th>"max"</th><td>{fmt(n.max)}</td></tr>
+                    <tr><th>"mean"</th><td>{fmt(n.mean)}</td></tr>
+                    <tr><th>"median"</th><td>{fmt(n.median)}</td></tr>
+                </table>
+                <div class="histogram">{bars}</div>
+                <div class="histogram-axis">
+                    <span>{fmt(n.min)}</span>
+                    <span>{fmt(n.max)}</span>
+                </div>
+                {outliers}
+            }
+        });
+
+        let values = stats.with_value(|s| {
+            (!s.values.is_empty()).then(|| {
+                let highest = s.values.first().map_or(1, |(_, m)| m.len());
+                let rows = s
+                    .values
+                    .iter()
+                    .take(MAX_VALUES)
+                    .map(|(value, members)| {
+                        #[allow(clippy::cast_precision_loss)]
+                        let width = members.len() as f64 / highest as f64 * 100.0;
+                        let pick = (value.to_string(), members.clone());
+
+                        view! {
+                            <tr on:click=move |_| picked.set(Some(pick.clone()))>
+                                <td>{value.to_string()}</td>
+                                <td>{members.len()}</td>
+                                <td class="frequency">
+                                    <span style:width=format!("{width}%")></span>
+                                </td>
+                            </tr>
+                        }
+                    })
+                    .collect_view();
+                let more = s.values.len().saturating_sub(MAX_VALUES);
+
+                view! {
+                    <table class="stats-values">
+                        <thead><tr><th>"value"</th><th>"count"</th><th></th></tr></thead>
+                        <tbody>
+                            {rows}
+                            {(more > 0).then(|| view! {
+                                <tr class="more"><td colspan="3">"and " {more} " more values"</td></tr>
+                            })}
+                        </tbody>
+                    </table>
+                }
+            })
+        });
+
+        let picked_view = move || {
+            picked.get().map(|(label, members)| {
+                let shown = members
+                    .iter()
+                    .take(MAX_QUERY_RESULTS)
+                    .map(|&idx| link(idx))
+                    .collect_view();
+                view! {
+                    <h4>{label}</h4>
+                    <ul class="stats-matches">{shown}</ul>
+                }
+            })
+        };
+
+        Some(
+            view! {
+                {summary}
+                {numbers}
+                {values}
+                {picked_view}
+            }
+            .into_any(),
+        )
+    };
+
+    view! {
+        <Show when=move || current.with(Option::is_some)>
+            <section class="property-stats">
+                <form method="get" on:submit=on_submit.clone()>
+                    <input
+                        type="text"
+                        name="stats"
+                        autocomplete="off"
+                        spellcheck="false"
+                        placeholder="data.raw.*.*.max_health"
+                        prop:value=input
+                        on:input=move |ev| input.set(event_target_value(&ev))
+                    />
+                    <button type="submit">"statistics"</button>
+                    <button type="button" on:click=close.clone()>"close"</button>
+                </form>
+                {body}
+            </section>
+        </Show>
+    }
+}
+
+fn value_label(m: &QueryMatch) -> String {
+    match &m.value {
+        DedupValue::String(s) => format!("\"{s}\""),
+        DedupValue::Number(n) => n.to_string(),
+        DedupValue::Bool(b) => b.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Rounds to a few significant digits, the raw values are shown in the lists.
+fn fmt(n: f64) -> String {
+    if n.abs() >= 1000.0 {
+        return format!("{n:.0}");
+    }
+
+    let rounded = format!("{n:.3}");
+    rounded
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
@@ -0,0 +1,176 @@
+use leptos::{ev::SubmitEvent, prelude::*};
+use leptos_router::{
+    components::A,
+    hooks::{use_location, use_navigate, use_query_map},
+};
+
+use crate::{
+    components::{reveal_href, stats_href},
+    util::{DedupValue, MAX_QUERY_RESULTS, Query, QueryMatch, preview},
+};
+
+/// Query box above the explorer, the query is kept in the `q` parameter so it can be shared.
+#[component]
+pub fn QueryPanel(data: DedupValue) -> impl IntoView {
+    let query = use_query_map();
+    let location = use_location();
+    let navigate = use_navigate();
+
+    let current = Memo::new(move |_| query.read().get("q").unwrap_or_default());
+    let input = RwSignal::new(String::new());
+    Effect::new(move |_| input.set(current.get()));
+
+    let on_submit = {
+        let location = location.clone();
+        let navigate = navigate.clone();
+        move |ev: SubmitEvent| {
+            ev.prevent_default();
+
+            let mut params = query.get_untracked();
+            params.remove("q");
+            let q = input.get_untracked();
+            if !q.trim().is_empty() {
+                params.insert("q", q.trim().to_string());
+            }
+
+            let href = format!(
+                "{}{}",
+                location.pathname.get_untracked(),
+                params.to_query_string()
+            );
+            navigate(&href, Default::default());
+        }
+    };
+
+    let show_stats = {
+        let location = location.clone();
+        move |_: leptos::ev::MouseEvent| {
+            let pattern = input.get_untracked();
+            navigate(
+                &stats_href(&location, Some(pattern.trim())),
+                Default::default(),
+            );
+        }
+    };
+
+    let data = StoredValue::new(data);
+    let results = move || {
+        let q = current.get();
+        if q.trim().is_empty() {
+            return ().into_any();
+        }
+
+        let parsed = match Query::parse(&q) {
+            Ok(parsed) => parsed,
+            Err(e) => return view! { <p class="query-error">{e.to_string()}</p> }.into_any(),
+        };
+        let (matches, truncated) = data.with_value(|d| parsed.run(d, MAX_QUERY_RESULTS));
+
+        let href = |m: &QueryMatch| reveal_href(&location, &m.path);
+
+        let summary = match (matches.len(), truncated) {
+            (_, true) => format!("showing the first {MAX_QUERY_RESULTS} results"),
+            (1, false) => "1 result".to_string(),
+            (n, false) => format!("{n} results"),
+        };
+
+        let list = if parsed.columns().is_empty() {
+            let items = matches
+                .iter()
+                .map(|m| {
+                    view! {
+                        <li>
+                            <A href=href(m)>{m.path.to_string()}</A>
+                            <span class="value">{cell(Some(&m.value))}</span>
+                        </li>
+                    }
+                })
+                .collect_view();
+
+            view! { <ol>{items}</ol> }.into_any()
+        } else {
+            let header = parsed
+                .columns()
+                .iter()
+                .map(|c| view! { <th>{c.to_string()}</th> })
+                .collect_view();
+            let rows = matches
+                .iter()
+                .map(|m| {
+                    let cells = parsed
+                        .columns()
+                        .iter()
+                        .map(|c| view! { <td>{cell(c.get(&m.value))}</td> })
+                        .collect_view();
+
+                    view! {
+                        <tr>
+                            <td><A href=href(m)>{m.path.to_string()}</A></td>
+                            {cells}
+                        </tr>
+                    }
+                })
+                .collect_view();
+
+            view! {
+                <table>
+                    <thead><tr><th>"path"</th>{header}</tr></thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            }
+            .into_any()
+        };
+
+        view! {
+            <p class="query-summary">{summary}</p>
+            <div class="query-results">{list}</div>
+        }
+        .into_any()
+    };
+
+    view! {
+        <section class="query">
+            <form method="get" on:submit=on_submit>
+                <input
+                    type="text"
+                    name="q"
+                    autocomplete="off"
+                    spellcheck="false"
+                    placeholder="data.raw.*.*[max_health > 1000 && resistances] | name, max_health"
+                    value=move || current.get()
+                    prop:value=input
+                    on:input=move |ev| input.set(event_target_value(&ev))
+                />
+                <button type="submit">"query"</button>
+                <button type="button" title="statistics of the selected values" on:click=show_stats>
+                    "statistics"
+                </button>
+            </form>
+            <details class="query-help">
+                <summary>"syntax"</summary>
+                <ul>
+                    <li><code>"recipe.iron-plate"</code> " selects a node, the " <code>"data.raw"</code> " prefix is optional"</li>
+                    <li><code>"*"</code> " matches every child, " <code>"**"</code> " every node below"</li>
+                    <li>
+                        <code>"[...]"</code> " keeps nodes matching a filter: " <code>"key"</code>
+                        " checks that it is set, compare with " <code>"== != < <= > >="</code>
+                        ", " <code>"~"</code> " for substrings, combine with " <code>"&& || ! ( )"</code>
+                    </li>
+                    <li><code>"| name, minable.result"</code> " shows these fields in a table"</li>
+                </ul>
+            </details>
+            {results}
+        </section>
+    }
+}
+
+fn cell(val: Option<&DedupValue>) -> String {
+    match val {
+        None => String::new(),
+        Some(DedupValue::String(s)) => s.to_string(),
+        Some(DedupValue::Null) => "null".to_string(),
+        Some(DedupValue::Bool(b)) => b.to_string(),
+        Some(DedupValue::Number(n)) => n.to_string(),
+        Some(val) => preview(val).unwrap_or_default(),
+    }
+}
@@ -0,0 +1,86 @@
+use leptos::prelude::*;
+use leptos_icons::Icon;
+use leptos_router::components::A;
+
+use crate::util::{HistoryStore, Visit, use_history};
+
+/// Pinned and recently opened variants as quick links below the mod selector.
+#[component]
+pub fn RecentMods() -> impl IntoView {
+    let history = use_history().get();
+
+    move || {
+        let history = history.read();
+        let links = history
+            .pinned
+            .iter()
+            .filter(|v| matches!(v, Visit::Variant { .. }))
+            .chain(history.recent_variants())
+            .map(|v| view! { <A href=v.href()>{v.label()}</A> })
+            .collect::<Vec<_>>();
+
+        (!links.is_empty()).then(|| {
+            view! {
+                <nav class="recent-mods">
+                    "recent: " {links}
+                </nav>
+            }
+        })
+    }
+}
+
+/// The whole history with buttons to pin and unpin entries.
+#[component]
+pub fn RecentList() -> impl IntoView {
+    let store = use_history();
+    let history = store.get();
+
+    move || {
+        let history = history.read();
+        let pinned = history.pinned.clone();
+        let variants = history.recent_variants().cloned().collect::<Vec<_>>();
+        let paths = history.recent_paths().cloned().collect::<Vec<_>>();
+
+        let section = move |title: &'static str, visits: Vec<Visit>, pinned: bool| {
+            (!visits.is_empty()).then(|| {
+                view! {
+                    <section>
+                        <h3>{title}</h3>
+                        <ul>
+                            {visits
+                                .into_iter()
+                                .map(|visit| view! { <VisitEntry store=store visit=visit pinned=pinned /> })
+                                .collect_view()}
+                        </ul>
+                    </section>
+                }
+            })
+        };
+
+        view! {
+            <div class="recent-list">
+                {section("Pinned", pinned, true)}
+                {section("Recent mods", variants, false)}
+                {section("Recent paths", paths, false)}
+            </div>
+        }
+    }
+}
+
+#[component]
+fn VisitEntry(store: HistoryStore, visit: Visit, pinned: bool) -> impl IntoView {
+    let (icon, title) = if pinned {
+        (icondata::BsPinAngleFill, "unpin")
+    } else {
+        (icondata::BsPinAngle, "pin")
+    };
+
+    view! {
+        <li>
+            <A href=visit.href()>{visit.label()}</A>
+            <button title=title on:click=move |_| store.toggle_pin(visit.clone())>
+                <Icon icon=icon width="0.875rem" height="0.875rem" />
+            </button>
+        </li>
+    }
+}
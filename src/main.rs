@@ -6,6 +6,10 @@ async fn main() {
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use raw_explorer::app::*;
+    use raw_explorer::util::{
+        LOCAL_DUMP_EVENTS, LocalDumpChanges, LocalDumps, ServerCaches, ServerConfig,
+        local_dump_events,
+    };
 
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
@@ -13,11 +17,38 @@ async fn main() {
     // Generate the list of routes in your Leptos App
     let routes = generate_route_list(App);
 
-    let app = Router::new()
-        .leptos_routes(&leptos_options, routes, {
-            let leptos_options = leptos_options.clone();
-            move || shell(leptos_options.clone())
-        })
+    let config = ServerConfig::load().expect("invalid raw-explorer config");
+    let public_config = config.public.clone();
+    let caches = ServerCaches::new(&config);
+    let local_dumps = config.local_dump_dir.clone().map(LocalDumps::new);
+    if let Some(local) = &local_dumps {
+        log!("serving local dumps from {}", local.dir().display());
+    }
+
+    let mut app = Router::new();
+    if let Some(local) = &local_dumps {
+        let changes = LocalDumpChanges::watch(local.clone(), config.local_poll_interval());
+        app = app
+            .route(LOCAL_DUMP_EVENTS, axum::routing::get(local_dump_events))
+            .layer(axum::Extension(changes));
+    }
+
+    let app = app
+        .leptos_routes_with_context(
+            &leptos_options,
+            routes,
+            move || {
+                provide_context(public_config.clone());
+                provide_context(caches.clone());
+                if let Some(local) = &local_dumps {
+                    provide_context(local.clone());
+                }
+            },
+            {
+                let leptos_options = leptos_options.clone();
+                move || shell(leptos_options.clone())
+            },
+        )
         .fallback(leptos_axum::file_and_error_handler(shell))
         .with_state(leptos_options);
 
@@ -1,7 +1,57 @@
+mod baseline;
+mod bookmarks;
+#[cfg(feature = "ssr")]
+mod cache;
+mod compare;
+mod config;
+mod dump_source;
+mod dump_worker;
+mod duplicates;
+mod error;
+mod expansion;
 mod fetch;
+mod fuzzy;
+mod history;
+#[cfg(feature = "hydrate")]
+mod idb;
 mod json_dedup;
+mod live_reload;
+mod node_path;
+mod offline;
+mod overview;
+mod packed;
+mod preview;
+mod query;
+mod selection;
+mod stats;
 mod type_helper;
+mod version;
 
+pub use baseline::*;
+pub use bookmarks::*;
+#[cfg(feature = "ssr")]
+pub use cache::*;
+pub use compare::*;
+pub use config::*;
+pub use dump_source::*;
+pub use dump_worker::*;
+pub use duplicates::*;
+pub use error::*;
+pub use expansion::*;
 pub use fetch::*;
+pub use fuzzy::*;
+pub use history::*;
+#[cfg(feature = "hydrate")]
+pub use idb::*;
 pub use json_dedup::*;
+pub use live_reload::*;
+pub use node_path::*;
+pub use offline::*;
+pub use overview::*;
+pub use packed::*;
+pub use preview::*;
+pub use query::*;
+pub use selection::*;
+pub use stats::*;
 pub use type_helper::*;
+pub use version::*;
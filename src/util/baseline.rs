@@ -0,0 +1,51 @@
+use super::DedupValue;
+
+/// How a node differs from the node at the same path in a baseline dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Unchanged,
+    Modified,
+    Added,
+}
+
+impl Change {
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Unchanged => "unchanged",
+            Self::Modified => "modified",
+            Self::Added => "added",
+        }
+    }
+}
+
+/// The value at the same path in another dump that a node gets compared against.
+#[derive(Debug, Clone, Default)]
+pub enum Baseline {
+    /// Nothing to compare against.
+    #[default]
+    None,
+    /// The baseline dump has no value at this path.
+    Missing,
+    Value(DedupValue),
+}
+
+impl Baseline {
+    pub fn child(&self, key: &str) -> Self {
+        match self {
+            Self::None => Self::None,
+            Self::Missing => Self::Missing,
+            Self::Value(v) => v
+                .child(key)
+                .map_or(Self::Missing, |c| Self::Value(c.clone())),
+        }
+    }
+
+    pub fn change(&self, val: &DedupValue) -> Option<Change> {
+        match self {
+            Self::None => None,
+            Self::Missing => Some(Change::Added),
+            Self::Value(base) if base.ptr_eq(val) || base == val => Some(Change::Unchanged),
+            Self::Value(_) => Some(Change::Modified),
+        }
+    }
+}
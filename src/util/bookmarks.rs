@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use codee::string::JsonSerdeCodec;
+use leptos::prelude::*;
+use leptos_use::storage::{UseStorageOptions, use_local_storage_with_options};
+
+use super::{NodePath, Visit};
+
+const STORAGE_KEY: &str = "raw-explorer-bookmarks";
+
+/// A node of a variant marked for later, with an optional note.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Bookmark {
+    pub variant: String,
+    pub path: NodePath,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl Bookmark {
+    pub fn is_at(&self, variant: &str, path: &NodePath) -> bool {
+        self.variant == variant && self.path == *path
+    }
+
+    pub fn visit(&self) -> Visit {
+        Visit::Path {
+            variant: self.variant.clone(),
+            path: self.path.clone(),
+        }
+    }
+}
+
+/// All bookmarks in the order they were added, also the export / import format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct Bookmarks(pub Vec<Bookmark>);
+
+impl Bookmarks {
+    pub fn get(&self, variant: &str, path: &NodePath) -> Option<&Bookmark> {
+        self.0.iter().find(|b| b.is_at(variant, path))
+    }
+
+    pub fn toggle(&mut self, variant: &str, path: &NodePath) {
+        if self.get(variant, path).is_some() {
+            self.remove(variant, path);
+        } else {
+            self.0.push(Bookmark {
+                variant: variant.to_string(),
+                path: path.clone(),
+                note: String::new(),
+            });
+        }
+    }
+
+    pub fn remove(&mut self, variant: &str, path: &NodePath) {
+        self.0.retain(|b| !b.is_at(variant, path));
+    }
+
+    pub fn set_note(&mut self, variant: &str, path: &NodePath, note: String) {
+        if let Some(bookmark) = self.0.iter_mut().find(|b| b.is_at(variant, path)) {
+            bookmark.note = note;
+        }
+    }
+
+    /// Adds imported bookmarks, notes of imported ones replace existing non-empty notes.
+    pub fn merge(&mut self, imported: Self) {
+        for bookmark in imported.0 {
+            match self
+                .0
+                .iter_mut()
+                .find(|b| b.is_at(&bookmark.variant, &bookmark.path))
+            {
+                Some(existing) if !bookmark.note.is_empty() => existing.note = bookmark.note,
+                Some(_) => {}
+                None => self.0.push(bookmark),
+            }
+        }
+    }
+}
+
+/// The [`Bookmarks`] persisted in local storage, provided by the [`App`](crate::app::App).
+#[derive(Debug, Clone, Copy)]
+pub struct BookmarkStore {
+    bookmarks: Signal<Bookmarks>,
+    set_bookmarks: WriteSignal<Bookmarks>,
+    /// Notes of the bookmarked nodes by variant and path, for lookups from every row.
+    notes: Memo<HashMap<String, HashMap<NodePath, String>>>,
+}
+
+impl BookmarkStore {
+    pub fn new() -> Self {
+        let (bookmarks, set_bookmarks, _) =
+            use_local_storage_with_options::<Bookmarks, JsonSerdeCodec>(
+                STORAGE_KEY,
+                UseStorageOptions::default().delay_during_hydration(true),
+            );
+
+        let notes = Memo::new(move |_| {
+            let mut notes = HashMap::<_, HashMap<_, _>>::new();
+            for b in bookmarks.read().0.iter() {
+                notes
+                    .entry(b.variant.clone())
+                    .or_default()
+                    .insert(b.path.clone(), b.note.clone());
+            }
+            notes
+        });
+
+        Self {
+            bookmarks,
+            set_bookmarks,
+            notes,
+        }
+    }
+
+    pub fn get(&self) -> Signal<Bookmarks> {
+        self.bookmarks
+    }
+
+    /// The note of a node while it is bookmarked.
+    ///
+    /// Only changes when this node's bookmark does, so rows don't re-render for other bookmarks.
+    pub fn note(&self, variant: Signal<String>, path: NodePath) -> Memo<Option<String>> {
+        let notes = self.notes;
+        Memo::new(move |_| notes.with(|n| n.get(&*variant.read())?.get(&path).cloned()))
+    }
+
+    pub fn update(&self, f: impl FnOnce(&mut Bookmarks)) {
+        self.set_bookmarks.update(f);
+    }
+}
+
+impl Default for BookmarkStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn use_bookmarks() -> BookmarkStore {
+    expect_context()
+}
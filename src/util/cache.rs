@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use fapi_diff::format::prototype::PrototypeDoc;
+
+use leptos::prelude::use_context;
+
+use super::{DumpSource, LoadError, PublicConfig, ServerConfig};
+
+/// A small in-memory cache whose entries expire after a fixed time.
+pub struct TtlCache<V> {
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, V)>>,
+}
+
+impl<V: Clone> TtlCache<V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<V> {
+        let entries = self.entries.lock().ok()?;
+        let (inserted, val) = entries.get(key)?;
+
+        (inserted.elapsed() < self.ttl).then(|| val.clone())
+    }
+
+    pub fn insert(&self, key: impl Into<String>, val: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+
+        entries.retain(|_, (inserted, _)| inserted.elapsed() < self.ttl);
+        while entries.len() >= self.capacity {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, (inserted, _))| *inserted)
+                .map(|(k, _)| k.clone())
+            else {
+                break;
+            };
+
+            entries.remove(&oldest);
+        }
+
+        entries.insert(key.into(), (Instant::now(), val));
+    }
+
+    /// Returns the cached value or inserts the result of `f` if it succeeds.
+    pub async fn get_or_try_insert<E, F: Future<Output = Result<V, E>>>(
+        &self,
+        key: &str,
+        f: impl FnOnce() -> F,
+    ) -> Result<V, E> {
+        if let Some(val) = self.get(key) {
+            return Ok(val);
+        }
+
+        let val = f().await?;
+        self.insert(key, val.clone());
+        Ok(val)
+    }
+}
+
+/// Caches shared by all server functions.
+#[derive(Clone)]
+pub struct ServerCaches {
+    pub mods: Arc<TtlCache<Vec<(String, String)>>>,
+    pub docs: Arc<TtlCache<PrototypeDoc>>,
+}
+
+impl ServerCaches {
+    pub fn new(config: &ServerConfig) -> Self {
+        Self {
+            mods: Arc::new(TtlCache::new(1, config.cache_ttl())),
+            docs: Arc::new(TtlCache::new(config.docs_cache_size, config.cache_ttl())),
+        }
+    }
+}
+
+/// Variants of the configured resolver, cached if the server provides [`ServerCaches`].
+pub async fn resolver_variants() -> Result<Vec<(String, String)>, LoadError> {
+    let resolver = use_context::<PublicConfig>().unwrap_or_default().resolver();
+    let Some(caches) = use_context::<ServerCaches>() else {
+        return resolver.variants().await;
+    };
+
+    caches
+        .mods
+        .get_or_try_insert("resolver", || async move { resolver.variants().await })
+        .await
+}
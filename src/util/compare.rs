@@ -0,0 +1,193 @@
+use std::{collections::BTreeSet, fmt, sync::Arc};
+
+use super::{DedupValue, NodePath, TypeHelper, preview};
+
+/// Most rows a comparison renders, deeper differences are cut off.
+pub const MAX_COMPARE_ROWS: usize = 5000;
+
+/// A node of some variant, written as `variant[/version]:path`,
+/// e.g. `base:data.raw.inserter.fast-inserter`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSpec {
+    pub variant: String,
+    pub version: Option<String>,
+    pub path: NodePath,
+}
+
+impl NodeSpec {
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (variant, path) = spec.trim().split_once(':')?;
+        let (variant, version) = match variant.split_once('/') {
+            Some((variant, version)) => (variant, Some(version.trim().to_string())),
+            None => (variant, None),
+        };
+
+        let variant = variant.trim();
+        if variant.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            variant: variant.to_string(),
+            version: version.filter(|v| !v.is_empty()),
+            path: NodePath::parse(path),
+        })
+    }
+}
+
+impl fmt::Display for NodeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.variant)?;
+        if let Some(version) = &self.version {
+            write!(f, "/{version}")?;
+        }
+        write!(f, ":{}", self.path)
+    }
+}
+
+/// One side of an [`AlignedRow`].
+#[derive(Debug, Clone)]
+pub struct Cell {
+    /// The value itself for scalars, a [`preview`] for arrays / objects.
+    pub value: String,
+    pub type_: Option<String>,
+}
+
+/// A key of the compared nodes with the values on both sides.
+#[derive(Debug, Clone)]
+pub struct AlignedRow {
+    pub key: Arc<str>,
+    pub depth: usize,
+    pub left: Option<Cell>,
+    pub right: Option<Cell>,
+    pub differs: bool,
+}
+
+/// Lines up two nodes key by key, starting with a row for the nodes themselves.
+///
+/// Equal subtrees are not expanded, the second value is `false` if [`MAX_COMPARE_ROWS`] was hit.
+pub fn align(
+    left: &DedupValue,
+    right: &DedupValue,
+    left_doc: Option<TypeHelper>,
+    right_doc: Option<TypeHelper>,
+) -> (Vec<AlignedRow>, bool) {
+    let mut rows = Vec::new();
+    let complete = push_row(
+        Arc::from(""),
+        Side::new(Some(left), left_doc),
+        Side::new(Some(right), right_doc),
+        0,
+        &mut rows,
+    );
+    (rows, complete)
+}
+
+struct Side<'a> {
+    val: Option<&'a DedupValue>,
+    doc: Option<TypeHelper>,
+}
+
+impl<'a> Side<'a> {
+    const fn new(val: Option<&'a DedupValue>, doc: Option<TypeHelper>) -> Self {
+        Self { val, doc }
+    }
+
+    fn child(&self, key: &str) -> Self {
+        let doc = self.doc.as_ref().map(|doc| match self.val {
+            Some(DedupValue::Array(arr)) => key.parse().map_or_else(
+                |_| doc.traverse_prop(key),
+                |idx| doc.traverse_idx(idx, arr.len()),
+            ),
+            _ => doc.traverse_prop(key),
+        });
+
+        Self::new(self.val.and_then(|v| v.child(key)), doc)
+    }
+
+    fn keys(&self) -> Vec<Arc<str>> {
+        match self.val {
+            Some(DedupValue::Array(arr)) => {
+                (0..arr.len()).map(|idx| idx.to_string().into()).collect()
+            }
+            Some(DedupValue::Object(obj)) => obj.keys().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn cell(&self) -> Option<Cell> {
+        let value = match self.val? {
+            DedupValue::Null => "null".to_string(),
+            DedupValue::Bool(b) => b.to_string(),
+            DedupValue::Number(n) => n.to_string(),
+            DedupValue::String(s) => format!("\"{s}\""),
+            val => preview(val).unwrap_or_default(),
+        };
+
+        Some(Cell {
+            value,
+            type_: self.doc.as_ref().and_then(TypeHelper::describe),
+        })
+    }
+
+    fn is_container(&self) -> bool {
+        matches!(self.val, Some(DedupValue::Array(_) | DedupValue::Object(_)))
+    }
+}
+
+/// Returns `false` once the row limit is reached.
+fn align_children(left: Side, right: Side, depth: usize, rows: &mut Vec<AlignedRow>) -> bool {
+    let mut keys = left.keys();
+    let right_keys = right.keys();
+
+    // arrays keep their order, object keys are merged in sorted order
+    if matches!(left.val, Some(DedupValue::Object(_)))
+        || matches!(right.val, Some(DedupValue::Object(_)))
+    {
+        keys = keys
+            .into_iter()
+            .chain(right_keys)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+    } else if right_keys.len() > keys.len() {
+        keys = right_keys;
+    }
+
+    keys.into_iter().all(|key| {
+        let (l, r) = (left.child(&key), right.child(&key));
+        push_row(key, l, r, depth, rows)
+    })
+}
+
+/// Adds the row of `key` and the rows below it if they differ, `false` once the row limit is reached.
+fn push_row(
+    key: Arc<str>,
+    left: Side,
+    right: Side,
+    depth: usize,
+    rows: &mut Vec<AlignedRow>,
+) -> bool {
+    if rows.len() >= MAX_COMPARE_ROWS {
+        return false;
+    }
+
+    let differs = match (left.val, right.val) {
+        (Some(a), Some(b)) => !a.ptr_eq(b) && a != b,
+        _ => true,
+    };
+
+    rows.push(AlignedRow {
+        key,
+        depth,
+        left: left.cell(),
+        right: right.cell(),
+        differs,
+    });
+
+    if differs && (left.is_container() || right.is_container()) {
+        return align_children(left, right, depth + 1, rows);
+    }
+
+    true
+}
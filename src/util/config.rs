@@ -0,0 +1,143 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use leptos::prelude::*;
+
+use super::Resolver;
+
+/// Settings the browser needs as well, shared through [`get_public_config`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PublicConfig {
+    pub resolver_url: String,
+    pub docs_url: String,
+    /// Mods that get a quick link in the mod selector, in display order.
+    pub featured_mods: Vec<String>,
+    /// Timeout for requests to the resolver and the docs in seconds.
+    pub request_timeout: u64,
+    /// Dump each mod's changes are shown against, e.g. `base` for `space-age`.
+    pub dependencies: BTreeMap<String, String>,
+}
+
+impl Default for PublicConfig {
+    fn default() -> Self {
+        Self {
+            resolver_url: "https://modname_resolver.bpbin.com".to_string(),
+            docs_url: "https://lua-api.factorio.com".to_string(),
+            featured_mods: ["base", "space-age", "quality", "recycler", "elevated-rails"]
+                .map(String::from)
+                .to_vec(),
+            request_timeout: 60,
+            dependencies: ["space-age", "quality", "recycler", "elevated-rails"]
+                .map(|m| (m.to_string(), "base".to_string()))
+                .into(),
+        }
+    }
+}
+
+impl PublicConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout)
+    }
+
+    pub fn resolver(&self) -> Resolver {
+        Resolver::new(self.resolver_url.as_str()).with_timeout(self.timeout())
+    }
+
+    pub fn is_featured(&self, name: &str) -> bool {
+        self.featured_mods.iter().any(|m| m == name)
+    }
+
+    pub fn dependency(&self, name: &str) -> Option<&str> {
+        self.dependencies.get(name).map(String::as_str)
+    }
+
+    /// Position of the mod in the featured list, used to sort featured mods first.
+    pub fn featured_rank(&self, name: &str) -> usize {
+        self.featured_mods
+            .iter()
+            .position(|m| m == name)
+            .unwrap_or(usize::MAX)
+    }
+}
+
+/// Everything configurable about the server.
+///
+/// Loaded from `raw-explorer.toml` (or the file named by `RAW_EXPLORER_CONFIG`),
+/// each setting can be overridden by a `RAW_EXPLORER_<SETTING>` environment variable.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    #[serde(flatten)]
+    pub public: PublicConfig,
+    /// Directory of local `<variant>.json` dumps served next to the resolver ones.
+    pub local_dump_dir: Option<std::path::PathBuf>,
+    /// How often local dumps are checked for changes in milliseconds.
+    pub local_poll_interval: u64,
+    /// How long the mod list and docs stay cached in seconds.
+    pub cache_ttl: u64,
+    /// How many docs versions are kept in memory.
+    pub docs_cache_size: usize,
+}
+
+#[cfg(feature = "ssr")]
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            public: PublicConfig::default(),
+            local_dump_dir: None,
+            local_poll_interval: 1000,
+            cache_ttl: 300,
+            docs_cache_size: 4,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl ServerConfig {
+    pub fn load() -> Result<Self, ::config::ConfigError> {
+        let file =
+            std::env::var("RAW_EXPLORER_CONFIG").unwrap_or_else(|_| "raw-explorer.toml".into());
+
+        ::config::Config::builder()
+            .add_source(::config::File::with_name(&file).required(false))
+            .add_source(
+                ::config::Environment::with_prefix("RAW_EXPLORER")
+                    .try_parsing(true)
+                    .list_separator(",")
+                    .with_list_parse_key("featured_mods"),
+            )
+            .build()?
+            .try_deserialize::<Self>()
+            .map(Self::with_legacy_env)
+    }
+
+    /// Still honors `LOCAL_DUMP_DIR`, the variable used before the settings got their prefix.
+    fn with_legacy_env(mut self) -> Self {
+        if let Some(dir) = std::env::var_os("LOCAL_DUMP_DIR") {
+            leptos::logging::warn!(
+                "LOCAL_DUMP_DIR is deprecated, set RAW_EXPLORER_LOCAL_DUMP_DIR or local_dump_dir instead"
+            );
+            self.local_dump_dir.get_or_insert_with(|| dir.into());
+        }
+        self
+    }
+
+    pub fn local_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.local_poll_interval)
+    }
+
+    pub fn cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.cache_ttl)
+    }
+}
+
+#[server]
+pub async fn get_public_config() -> Result<PublicConfig, ServerFnError> {
+    Ok(use_context::<PublicConfig>().unwrap_or_default())
+}
+
+/// The [`PublicConfig`] loaded once by the [`App`](crate::app::App).
+pub fn use_public_config() -> Resource<PublicConfig> {
+    expect_context()
+}
@@ -0,0 +1,343 @@
+use std::{sync::Arc, time::Duration};
+
+use leptos::prelude::*;
+
+use super::{DedupValue, DumpProgress, LoadError, fetch_data};
+
+/// Somewhere `data.raw` dumps can be loaded from.
+pub trait DumpSource {
+    /// Lists all variants of this source as `(name, version)` pairs.
+    fn variants(&self) -> impl Future<Output = Result<Vec<(String, String)>, LoadError>>;
+
+    /// Loads the dump of a single variant.
+    fn dump(&self, variant: &str) -> impl Future<Output = Result<DedupValue, LoadError>>;
+}
+
+/// Dumps processed by the modname resolver.
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    base_url: Arc<str>,
+    timeout: Duration,
+}
+
+impl Resolver {
+    pub fn new(base_url: impl Into<Arc<str>>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn url(&self, uri: &str) -> String {
+        format!("{}/{uri}", self.base_url)
+    }
+
+    pub async fn fetch<T: serde::de::DeserializeOwned>(&self, uri: &str) -> Result<T, LoadError> {
+        fetch_data(&self.url(uri), self.timeout).await
+    }
+
+    /// Like [`DumpSource::dump`], but downloaded and parsed in a web worker when possible
+    /// and kept in the browser's IndexedDB as `resolver/<variant>[_<version>]`.
+    #[cfg_attr(not(feature = "hydrate"), allow(unused_variables))]
+    pub async fn dump_with_progress(
+        &self,
+        variant: &str,
+        progress: Option<WriteSignal<DumpProgress>>,
+    ) -> Result<Dump, LoadError> {
+        #[cfg(feature = "hydrate")]
+        {
+            let job = super::DumpJob::get(self.url(&format!("raw/{variant}")), self.timeout)
+                .cached_as(format!("resolver/{variant}"));
+            if let Some(res) = super::load_in_worker(&job, progress).await {
+                return missing_dump(variant, res).map(|dump| Dump {
+                    data: dump.data,
+                    origin: DumpOrigin::Resolver,
+                    offline: dump.offline,
+                });
+            }
+        }
+
+        self.dump(variant).await.map(|data| Dump {
+            data,
+            origin: DumpOrigin::Resolver,
+            offline: false,
+        })
+    }
+}
+
+fn missing_dump<T>(variant: &str, res: Result<T, LoadError>) -> Result<T, LoadError> {
+    match res {
+        Err(LoadError::Status { status: 404, .. }) => Err(LoadError::NotFound(variant.into())),
+        res => res,
+    }
+}
+
+impl DumpSource for Resolver {
+    async fn variants(&self) -> Result<Vec<(String, String)>, LoadError> {
+        #[derive(serde::Deserialize)]
+        struct ProcessedMods {
+            processed: std::collections::BTreeSet<String>,
+        }
+
+        let stats = self.fetch::<ProcessedMods>("stats").await?;
+        let res = stats
+            .processed
+            .iter()
+            .filter_map(|r| {
+                let (name, version) = r.rsplit_once('_')?;
+                Some((name.to_string(), version.to_string()))
+            })
+            .collect();
+
+        Ok(res)
+    }
+
+    async fn dump(&self, variant: &str) -> Result<DedupValue, LoadError> {
+        missing_dump(variant, self.fetch(&format!("raw/{variant}")).await)
+    }
+}
+
+/// Version reported for all local dumps.
+pub const LOCAL_VERSION: &str = "local";
+
+/// Dumps stored as `<variant>.json` files in a local directory,
+/// e.g. a renamed `data-raw-dump.json` from `factorio --dump-data`.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone)]
+pub struct LocalDumps {
+    dir: Arc<std::path::Path>,
+    /// Prototype count per variant together with the modification time it was counted at.
+    counts:
+        Arc<std::sync::Mutex<std::collections::HashMap<String, (std::time::SystemTime, usize)>>>,
+}
+
+#[cfg(feature = "ssr")]
+impl LocalDumps {
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            dir: dir.into().into(),
+            counts: Arc::default(),
+        }
+    }
+
+    pub fn dir(&self) -> &std::path::Path {
+        &self.dir
+    }
+
+    fn path_of(&self, variant: &str) -> Option<std::path::PathBuf> {
+        // only plain file names, no way to escape the dump directory
+        if variant.is_empty() || variant.starts_with('.') || variant.contains(['/', '\\']) {
+            return None;
+        }
+
+        Some(self.dir.join(format!("{variant}.json")))
+    }
+
+    pub async fn contains(&self, variant: &str) -> bool {
+        let Some(path) = self.path_of(variant) else {
+            return false;
+        };
+
+        tokio::fs::try_exists(path).await.unwrap_or(false)
+    }
+
+    /// Number of prototypes in a local dump, counted without building the full dump.
+    pub async fn prototype_count(&self, variant: &str) -> Option<usize> {
+        use std::collections::HashMap;
+
+        use serde::de::IgnoredAny;
+
+        let path = self.path_of(variant)?;
+        let modified = tokio::fs::metadata(&path).await.ok()?.modified().ok()?;
+
+        if let Some((counted_at, count)) = self.counts.lock().ok()?.get(variant)
+            && *counted_at == modified
+        {
+            return Some(*count);
+        }
+
+        let raw = tokio::fs::read(path).await.ok()?;
+        let types =
+            serde_json::from_slice::<HashMap<String, HashMap<String, IgnoredAny>>>(&raw).ok()?;
+        let count = types.values().map(HashMap::len).sum();
+
+        self.counts
+            .lock()
+            .ok()?
+            .insert(variant.to_string(), (modified, count));
+        Some(count)
+    }
+
+    /// Last modification time of every dump in the directory.
+    pub async fn modified(&self) -> Result<Vec<(String, std::time::SystemTime)>, LoadError> {
+        let mut res = Vec::new();
+        for (variant, _) in self.variants().await? {
+            let Some(path) = self.path_of(&variant) else {
+                continue;
+            };
+
+            let Ok(meta) = tokio::fs::metadata(path).await else {
+                continue;
+            };
+
+            let modified = meta
+                .modified()
+                .map_err(|e| LoadError::Server(e.to_string()))?;
+            res.push((variant, modified));
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl DumpSource for LocalDumps {
+    async fn variants(&self) -> Result<Vec<(String, String)>, LoadError> {
+        let io_err = |e: std::io::Error| LoadError::Server(e.to_string());
+        let mut entries = tokio::fs::read_dir(&self.dir).await.map_err(io_err)?;
+
+        let mut res = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(io_err)? {
+            let path = entry.path();
+            if path.extension().is_none_or(|e| e != "json") {
+                continue;
+            }
+
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            if self.path_of(name).is_some() {
+                res.push((name.to_string(), LOCAL_VERSION.to_string()));
+            }
+        }
+
+        Ok(res)
+    }
+
+    async fn dump(&self, variant: &str) -> Result<DedupValue, LoadError> {
+        let path = self
+            .path_of(variant)
+            .ok_or_else(|| LoadError::NotFound(variant.into()))?;
+
+        let raw = tokio::fs::read(path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => LoadError::NotFound(variant.into()),
+            _ => LoadError::Server(e.to_string()),
+        })?;
+
+        Ok(serde_json::from_slice(&raw)?)
+    }
+}
+
+/// Where a loaded dump came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpOrigin {
+    Resolver,
+    Local,
+}
+
+#[derive(Debug, Clone)]
+pub struct Dump {
+    pub data: DedupValue,
+    pub origin: DumpOrigin,
+    /// Taken from the browser's cache because the server could not be reached.
+    pub offline: bool,
+}
+
+/// Loads a dump, preferring the server's local dump directory over the resolver
+/// unless a specific resolver version is requested.
+///
+/// In the browser the dump is parsed in a web worker which reports to `progress`.
+pub async fn get_dump(
+    resolver: Resolver,
+    variant: String,
+    version: Option<String>,
+    progress: Option<WriteSignal<DumpProgress>>,
+) -> Result<Dump, LoadError> {
+    if version.as_deref().is_none_or(|v| v == LOCAL_VERSION) {
+        match local_dump(variant.clone(), resolver.timeout, progress).await {
+            Ok(Some(dump)) => return Ok(dump),
+            // without a connection to the server the resolver dump might still be cached
+            Ok(None) | Err(LoadError::Network(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    match version {
+        Some(version) => {
+            resolver
+                .dump_with_progress(&format!("{variant}_{version}"), progress)
+                .await
+        }
+        None => resolver.dump_with_progress(&variant, progress).await,
+    }
+}
+
+/// [`get_local_dump`], called from a web worker when [`has_local_dump`] finds one.
+///
+/// Unlike resolver dumps these are not kept in the browser's IndexedDB, the server function
+/// has no `ETag` to revalidate a copy against and local dumps are rewritten all the time.
+#[cfg_attr(not(feature = "hydrate"), allow(unused_variables))]
+async fn local_dump(
+    variant: String,
+    timeout: Duration,
+    progress: Option<WriteSignal<DumpProgress>>,
+) -> Result<Option<Dump>, LoadError> {
+    #[cfg(feature = "hydrate")]
+    {
+        use leptos::server_fn::ServerFn;
+
+        // most servers have no local dumps, asking is a lot cheaper than starting a worker
+        if !has_local_dump(variant.clone()).await? {
+            return Ok(None);
+        }
+
+        // same request as the server function client, which answers `null` without a local dump
+        let variant_param = String::from(js_sys::encode_uri_component(&variant));
+        let job = super::DumpJob::get(GetLocalDump::PATH, timeout)
+            .with_form(format!("variant={variant_param}"));
+        if let Some(res) = super::load_in_worker(&job, progress).await {
+            return res.map(|dump| {
+                (dump.data != DedupValue::Null).then_some(Dump {
+                    data: dump.data,
+                    origin: DumpOrigin::Local,
+                    offline: dump.offline,
+                })
+            });
+        }
+    }
+
+    let dump = get_local_dump(variant).await?;
+    Ok(dump.map(|data| Dump {
+        data,
+        origin: DumpOrigin::Local,
+        offline: false,
+    }))
+}
+
+#[server]
+pub async fn has_local_dump(variant: String) -> Result<bool, LoadError> {
+    match use_context::<LocalDumps>() {
+        Some(local) => Ok(local.contains(&variant).await),
+        None => Ok(false),
+    }
+}
+
+#[server]
+pub async fn get_local_dump(variant: String) -> Result<Option<DedupValue>, LoadError> {
+    let Some(local) = use_context::<LocalDumps>() else {
+        return Ok(None);
+    };
+
+    if !local.contains(&variant).await {
+        return Ok(None);
+    }
+
+    local.dump(&variant).await.map(Some)
+}
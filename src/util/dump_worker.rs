@@ -0,0 +1,489 @@
+#[cfg(feature = "hydrate")]
+use leptos::prelude::*;
+
+use super::{DedupValue, LoadError};
+
+/// Script of the worker, loads the app's wasm bundle and hands every message to `run_dump_job`.
+pub const DUMP_WORKER: &str = "/dump-worker.js";
+
+/// How far a dump loaded in the background got.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DumpProgress {
+    pub downloaded: usize,
+    /// Size announced by the server, can be smaller than `downloaded` for compressed responses.
+    pub total: Option<usize>,
+    /// Percentage of the downloaded bytes parsed so far, `None` while still downloading.
+    pub parsed: Option<u8>,
+}
+
+/// Most dumps kept in the browser's IndexedDB, the least recently used ones are dropped.
+pub const MAX_CACHED_DUMPS: usize = 8;
+
+/// Cache entry listing the keys of the cached dumps, most recently used first.
+#[cfg(feature = "hydrate")]
+const RECENT_DUMPS: &str = "recent-dumps";
+
+/// A download for the worker, the response is parsed into a [`DedupValue`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DumpJob {
+    url: String,
+    /// URL encoded form sent with a POST request instead of a plain GET.
+    form: Option<String>,
+    timeout_ms: u64,
+    /// Where the dump is kept in the browser's IndexedDB, if anywhere.
+    cache_key: Option<String>,
+}
+
+impl DumpJob {
+    pub fn get(url: impl Into<String>, timeout: std::time::Duration) -> Self {
+        Self {
+            url: url.into(),
+            form: None,
+            timeout_ms: u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+            cache_key: None,
+        }
+    }
+
+    /// Keeps the dump in the browser's IndexedDB, revalidated with its `ETag` (or a stand-in)
+    /// on later loads and used as is when the network is unavailable.
+    #[must_use]
+    pub fn cached_as(mut self, key: impl Into<String>) -> Self {
+        self.cache_key = Some(key.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_form(mut self, form: impl Into<String>) -> Self {
+        self.form = Some(form.into());
+        self
+    }
+}
+
+#[cfg(feature = "hydrate")]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+enum WorkerMessage {
+    Progress(DumpProgress),
+    /// Sent together with the [`pack`](super::pack)ed dump.
+    Done {
+        offline: bool,
+    },
+    Failed(LoadError),
+}
+
+/// Reads a buffer and reports the percentage consumed so far whenever it grows.
+#[cfg(feature = "hydrate")]
+struct ProgressReader<'a, F> {
+    buf: &'a [u8],
+    total: usize,
+    reported: u8,
+    report: F,
+}
+
+#[cfg(feature = "hydrate")]
+impl<'a, F: FnMut(u8)> ProgressReader<'a, F> {
+    const fn new(buf: &'a [u8], report: F) -> Self {
+        Self {
+            buf,
+            total: buf.len(),
+            reported: 0,
+            report,
+        }
+    }
+}
+
+#[cfg(feature = "hydrate")]
+impl<F: FnMut(u8)> std::io::Read for ProgressReader<'_, F> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        let read = std::io::Read::read(&mut self.buf, out)?;
+
+        let done = (self.total - self.buf.len()) * 100 / self.total.max(1);
+        let percent = u8::try_from(done).unwrap_or(100);
+        if percent > self.reported {
+            self.reported = percent;
+            (self.report)(percent);
+        }
+
+        Ok(read)
+    }
+}
+
+/// A dump loaded by [`load_in_worker`].
+#[cfg(feature = "hydrate")]
+#[derive(Debug, Clone)]
+pub struct WorkerDump {
+    pub data: DedupValue,
+    /// Taken from the browser's cache because the server could not be reached.
+    pub offline: bool,
+}
+
+/// Loads a dump in a web worker so the page stays responsive, reporting to `progress`.
+///
+/// Returns `None` if no worker could be used, callers should load the dump themselves then.
+#[cfg(feature = "hydrate")]
+pub async fn load_in_worker(
+    job: &DumpJob,
+    progress: Option<WriteSignal<DumpProgress>>,
+) -> Option<Result<WorkerDump, LoadError>> {
+    use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+
+    /// Stops the worker when the load is dropped, e.g. because another variant was opened.
+    struct Terminate(web_sys::Worker);
+
+    impl Drop for Terminate {
+        fn drop(&mut self) {
+            self.0.terminate();
+        }
+    }
+
+    let options = web_sys::WorkerOptions::new();
+    options.set_type(web_sys::WorkerType::Module);
+    let worker = Terminate(web_sys::Worker::new_with_options(DUMP_WORKER, &options).ok()?);
+
+    if let Some(progress) = progress {
+        progress.try_set(DumpProgress::default());
+    }
+
+    let mut handlers = None;
+    let finished = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new({
+            let resolve = resolve.clone();
+            move |ev: web_sys::MessageEvent| {
+                let msg = ev
+                    .data()
+                    .dyn_into::<js_sys::Array>()
+                    .ok()
+                    .and_then(|data| data.get(0).as_string());
+                match msg.and_then(|msg| serde_json::from_str(&msg).ok()) {
+                    Some(WorkerMessage::Progress(p)) => {
+                        if let Some(progress) = progress {
+                            progress.try_set(p);
+                        }
+                    }
+                    _ => {
+                        let _ = resolve.call1(&JsValue::NULL, &ev.data());
+                    }
+                }
+            }
+        });
+        // the worker failed to start or crashed, rejecting falls back to loading without it
+        let on_error =
+            Closure::<dyn Fn(web_sys::ErrorEvent)>::new(move |_: web_sys::ErrorEvent| {
+                let _ = reject.call0(&JsValue::NULL);
+            });
+
+        worker
+            .0
+            .set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        worker
+            .0
+            .set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        handlers = Some((on_message, on_error));
+    });
+
+    let job = serde_json::to_string(job).ok()?;
+    worker.0.post_message(&JsValue::from_str(&job)).ok()?;
+
+    let data = wasm_bindgen_futures::JsFuture::from(finished).await.ok()?;
+    drop(handlers);
+
+    let data = data.dyn_into::<js_sys::Array>().ok()?;
+    match serde_json::from_str(&data.get(0).as_string()?).ok()? {
+        WorkerMessage::Done { offline } => {
+            let packed = data.get(1).dyn_into::<js_sys::ArrayBuffer>().ok()?;
+            let packed = js_sys::Uint8Array::new(&packed).to_vec();
+            let data = super::unpack(&packed)?;
+            Some(Ok(WorkerDump { data, offline }))
+        }
+        WorkerMessage::Failed(e) => Some(Err(e)),
+        WorkerMessage::Progress(_) => None,
+    }
+}
+
+/// Entry point of the [`DUMP_WORKER`], runs a serialized [`DumpJob`] and posts the progress
+/// and the [`pack`](super::pack)ed result back, transferring its buffer instead of copying it.
+#[cfg(feature = "hydrate")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub async fn run_dump_job(job: String) {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let scope = js_sys::global().unchecked_into::<web_sys::DedicatedWorkerGlobalScope>();
+    let post = |msg: &WorkerMessage| {
+        if let Ok(msg) = serde_json::to_string(msg) {
+            let _ = scope.post_message(&js_sys::Array::of1(&JsValue::from_str(&msg)));
+        }
+    };
+
+    let res = match serde_json::from_str::<DumpJob>(&job) {
+        Ok(job) => job.run(&scope, &post).await,
+        Err(e) => Err(LoadError::Server(e.to_string())),
+    };
+
+    let (packed, offline) = match res {
+        Ok((packed, offline)) => (packed.buffer(), offline),
+        Err(e) => {
+            post(&WorkerMessage::Failed(e));
+            return;
+        }
+    };
+
+    let Ok(done) = serde_json::to_string(&WorkerMessage::Done { offline }) else {
+        return;
+    };
+    let _ = scope.post_message_with_transfer(
+        &js_sys::Array::of2(&JsValue::from_str(&done), &packed),
+        &js_sys::Array::of1(&packed),
+    );
+}
+
+/// Result of a download that might have been answered from the cache.
+#[cfg(feature = "hydrate")]
+enum Fetched {
+    /// The [`validator`] still matches the cached dump.
+    Unchanged,
+    Dump {
+        value: DedupValue,
+        etag: Option<String>,
+    },
+}
+
+#[cfg(feature = "hydrate")]
+impl DumpJob {
+    /// Loads the [`pack`](super::pack)ed dump, from the cache if it is unchanged upstream
+    /// or the network is unavailable, the latter is reported as `true`.
+    async fn run(
+        self,
+        scope: &web_sys::DedicatedWorkerGlobalScope,
+        post: &impl Fn(&WorkerMessage),
+    ) -> Result<(js_sys::Uint8Array, bool), LoadError> {
+        use super::{CacheEntry, IdbCache};
+
+        let cache = match &self.cache_key {
+            Some(key) => IdbCache::open().await.map(|cache| (cache, key.as_str())),
+            None => None,
+        };
+        let cached = match &cache {
+            Some((cache, key)) => cache.get(key).await,
+            None => None,
+        };
+
+        let known_etag = cached.as_ref().and_then(|c| c.etag.as_deref());
+        let (packed, fresh, offline) = match (self.fetch(scope, known_etag, post).await, cached) {
+            // nothing worth keeping, e.g. the answer that there is no local dump
+            (Ok(Fetched::Dump { value, .. }), _) if value == DedupValue::Null => {
+                let packed = js_sys::Uint8Array::from(super::pack(&value).as_slice());
+                return Ok((packed, false));
+            }
+            (Ok(Fetched::Dump { value, etag }), _) => {
+                let packed = js_sys::Uint8Array::from(super::pack(&value).as_slice());
+                let entry = CacheEntry {
+                    etag,
+                    data: packed.clone(),
+                };
+                (packed, Some(entry), false)
+            }
+            (Ok(Fetched::Unchanged), Some(cached)) => (cached.data, None, false),
+            (Err(e), Some(cached)) if e.is_transient() => (cached.data, None, true),
+            (Ok(Fetched::Unchanged), None) => {
+                return Err(LoadError::Server(
+                    "unchanged dump without a cached copy".into(),
+                ));
+            }
+            (Err(e), _) => return Err(e),
+        };
+
+        if let Some((cache, key)) = &cache {
+            if let Some(entry) = fresh {
+                cache.put(key, &entry).await;
+            }
+            remember(cache, key).await;
+        }
+
+        Ok((packed, offline))
+    }
+
+    async fn fetch(
+        &self,
+        scope: &web_sys::DedicatedWorkerGlobalScope,
+        known_etag: Option<&str>,
+        post: &impl Fn(&WorkerMessage),
+    ) -> Result<Fetched, LoadError> {
+        use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+        use wasm_bindgen_futures::JsFuture;
+
+        let js_err =
+            |e: JsValue| LoadError::Network(e.as_string().unwrap_or_else(|| format!("{e:?}")));
+
+        let abort = web_sys::AbortController::new().map_err(js_err)?;
+        let send = |method: &str| -> Result<JsFuture, LoadError> {
+            let init = web_sys::RequestInit::new();
+            init.set_signal(Some(&abort.signal()));
+            init.set_method(method);
+            if let Some(form) = &self.form {
+                let headers = web_sys::Headers::new().map_err(js_err)?;
+                headers
+                    .set("content-type", "application/x-www-form-urlencoded")
+                    .map_err(js_err)?;
+                init.set_headers(&headers);
+                init.set_body(&JsValue::from_str(form));
+            }
+
+            Ok(JsFuture::from(
+                scope.fetch_with_str_and_init(&self.url, &init),
+            ))
+        };
+
+        // the worker is terminated after every job, so the closure doesn't need to be cleaned up
+        let timeout = Closure::once_into_js({
+            let abort = abort.clone();
+            move || abort.abort()
+        });
+        scope
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                timeout.unchecked_ref(),
+                i32::try_from(self.timeout_ms).unwrap_or(i32::MAX),
+            )
+            .map_err(js_err)?;
+
+        // revalidated with a HEAD request, `If-None-Match` would need a CORS preflight
+        if let Some(known) = known_etag
+            && self.form.is_none()
+        {
+            let head = send("HEAD")?
+                .await
+                .map_err(js_err)?
+                .unchecked_into::<web_sys::Response>();
+            if head.ok() && validator(&head.headers()).as_deref() == Some(known) {
+                return Ok(Fetched::Unchanged);
+            }
+        }
+
+        let method = if self.form.is_some() { "POST" } else { "GET" };
+        let resp = send(method)?
+            .await
+            .map_err(js_err)?
+            .unchecked_into::<web_sys::Response>();
+
+        if !resp.ok() {
+            let text = match resp.text() {
+                Ok(text) => JsFuture::from(text).await.ok().and_then(|t| t.as_string()),
+                Err(_) => None,
+            }
+            .unwrap_or_default();
+
+            // server functions answer with the error they returned
+            if let Ok(e) = serde_json::from_str::<LoadError>(&text) {
+                return Err(e);
+            }
+
+            let message = if text.is_empty() {
+                resp.status_text()
+            } else {
+                text
+            };
+            return Err(LoadError::Status {
+                status: resp.status(),
+                message,
+            });
+        }
+
+        // servers that don't answer HEAD requests still get the download cut short
+        let etag = validator(&resp.headers());
+        if known_etag.is_some() && etag.as_deref() == known_etag {
+            abort.abort();
+            return Ok(Fetched::Unchanged);
+        }
+
+        let mut progress = DumpProgress {
+            total: resp
+                .headers()
+                .get("content-length")
+                .ok()
+                .flatten()
+                .and_then(|len| len.parse().ok()),
+            ..DumpProgress::default()
+        };
+
+        let mut raw = Vec::new();
+        if let Some(body) = resp.body() {
+            let reader = body
+                .get_reader()
+                .unchecked_into::<web_sys::ReadableStreamDefaultReader>();
+
+            loop {
+                let chunk = JsFuture::from(reader.read()).await.map_err(js_err)?;
+                let done =
+                    js_sys::Reflect::get(&chunk, &JsValue::from_str("done")).map_err(js_err)?;
+                if done.is_truthy() {
+                    break;
+                }
+
+                let bytes = js_sys::Reflect::get(&chunk, &JsValue::from_str("value"))
+                    .map_err(js_err)?
+                    .unchecked_into::<js_sys::Uint8Array>();
+                let start = raw.len();
+                raw.resize(start + bytes.length() as usize, 0);
+                bytes.copy_to(&mut raw[start..]);
+
+                progress.downloaded = raw.len();
+                post(&WorkerMessage::Progress(progress));
+            }
+        }
+
+        // slower than parsing the slice at once, but the page is not waiting on it anymore
+        let reader = ProgressReader::new(&raw, |parsed| {
+            progress.parsed = Some(parsed);
+            post(&WorkerMessage::Progress(progress));
+        });
+        let value = serde_json::from_reader(std::io::BufReader::new(reader))?;
+        Ok(Fetched::Dump { value, etag })
+    }
+}
+
+/// Tells whether a response changed since a cached copy, its `ETag` if the browser lets us
+/// read it.
+///
+/// Responses from another origin, like the resolver, only expose the `ETag` with an
+/// `Access-Control-Expose-Headers: ETag` header, which can't be relied on. `Last-Modified`
+/// and `Content-Length` are always readable, so they are used instead when it is hidden.
+#[cfg(feature = "hydrate")]
+fn validator(headers: &web_sys::Headers) -> Option<String> {
+    let get = |name: &str| headers.get(name).ok().flatten();
+
+    if let Some(etag) = get("etag") {
+        return Some(etag);
+    }
+
+    match (get("last-modified"), get("content-length")) {
+        (None, None) => None,
+        (modified, length) => Some(format!(
+            "{} {}",
+            modified.unwrap_or_default(),
+            length.unwrap_or_default()
+        )),
+    }
+}
+
+/// Marks `key` as the most recently used dump and drops those beyond [`MAX_CACHED_DUMPS`].
+#[cfg(feature = "hydrate")]
+async fn remember(cache: &super::IdbCache, key: &str) {
+    let mut recent = match cache.get(RECENT_DUMPS).await {
+        Some(entry) => {
+            serde_json::from_slice::<Vec<String>>(&entry.data.to_vec()).unwrap_or_default()
+        }
+        None => Vec::new(),
+    };
+
+    recent.retain(|k| k != key);
+    recent.insert(0, key.to_string());
+    for evicted in recent.split_off(MAX_CACHED_DUMPS.min(recent.len())) {
+        cache.delete(&evicted).await;
+    }
+
+    if let Ok(list) = serde_json::to_vec(&recent) {
+        let entry = super::CacheEntry {
+            etag: None,
+            data: js_sys::Uint8Array::from(list.as_slice()),
+        };
+        cache.put(RECENT_DUMPS, &entry).await;
+    }
+}
@@ -0,0 +1,92 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::{DedupValue, JsonSizes, NodePath};
+
+/// Most places remembered per duplicate, the rest is only counted.
+const MAX_LOCATIONS: usize = 50;
+
+/// A subtree stored once but used at several places of the dump.
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    pub value: DedupValue,
+    pub size: usize,
+    pub count: usize,
+    /// The first [`MAX_LOCATIONS`] places it occurs at.
+    pub locations: Vec<NodePath>,
+}
+
+impl Duplicate {
+    /// JSON size the dump would take up without sharing this subtree.
+    pub const fn saved(&self) -> usize {
+        self.size * (self.count - 1)
+    }
+}
+
+/// Finds the subtrees that occur more than once, those saving the most first.
+///
+/// Relies on the parser interning equal subtrees, occurrences of the same subtree
+/// are told apart by their allocation. Shared subtrees are not searched again, so
+/// parts of a duplicate are only reported if they also occur elsewhere.
+pub fn find_duplicates(root: &DedupValue, limit: usize) -> Vec<Duplicate> {
+    let mut seen = HashMap::new();
+    walk(root, &mut Vec::new(), &mut seen);
+
+    let mut duplicates = seen
+        .into_values()
+        .filter(|d: &Duplicate| d.count > 1)
+        .collect::<Vec<_>>();
+    // duplicates nest, measuring them together shares the work for their common parts
+    let mut sizes = JsonSizes::default();
+    for d in &mut duplicates {
+        d.size = sizes.of(&d.value);
+    }
+    duplicates.sort_unstable_by_key(|d| std::cmp::Reverse(d.saved()));
+    duplicates.truncate(limit);
+    duplicates
+}
+
+fn walk(val: &DedupValue, path: &mut Vec<Arc<str>>, seen: &mut HashMap<*const (), Duplicate>) {
+    let ptr = match val {
+        DedupValue::Array(a) => Arc::as_ptr(a).cast::<()>(),
+        DedupValue::Object(o) => std::ptr::from_ref(&**o).cast::<()>(),
+        _ => return,
+    };
+
+    if let Some(known) = seen.get_mut(&ptr) {
+        known.count += 1;
+        if known.locations.len() < MAX_LOCATIONS {
+            known.locations.push(path.iter().cloned().collect());
+        }
+        return;
+    }
+
+    seen.insert(
+        ptr,
+        Duplicate {
+            value: val.clone(),
+            // only computed for actual duplicates
+            size: 0,
+            count: 1,
+            locations: vec![path.iter().cloned().collect()],
+        },
+    );
+
+    let mut visit = |key: Arc<str>, child: &DedupValue, seen: &mut HashMap<_, _>| {
+        path.push(key);
+        walk(child, path, seen);
+        path.pop();
+    };
+    match val {
+        DedupValue::Array(a) => {
+            for (idx, child) in a.iter().enumerate() {
+                visit(idx.to_string().into(), child, seen);
+            }
+        }
+        DedupValue::Object(o) => {
+            for (key, child) in o.iter() {
+                visit(key.clone(), child, seen);
+            }
+        }
+        _ => {}
+    }
+}
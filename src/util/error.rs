@@ -0,0 +1,82 @@
+use leptos::{
+    prelude::{FromServerFnError, ServerFnErrorErr},
+    server_fn::codec::JsonEncoding,
+};
+
+/// Everything that can go wrong while loading dumps, mod lists or docs.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, serde::Serialize, serde::Deserialize)]
+pub enum LoadError {
+    /// The request did not get any response.
+    #[error("network error: {0}")]
+    Network(String),
+    /// The upstream server responded with an error status.
+    #[error("request failed with status {status}: {message}")]
+    Status { status: u16, message: String },
+    /// There is no dump for this variant.
+    #[error("no dump found for `{0}`")]
+    NotFound(String),
+    /// The response is not valid JSON or doesn't match the expected structure.
+    #[error("malformed JSON at line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("API docs unavailable: {0}")]
+    DocsUnavailable(String),
+    /// Failure of the server function itself.
+    #[error("{0}")]
+    Server(String),
+}
+
+impl LoadError {
+    /// Errors that might go away when trying again.
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Server(_) | Self::DocsUnavailable(_) => true,
+            Self::Status { status, .. } => *status == 429 || *status >= 500,
+            Self::NotFound(_) | Self::Parse { .. } => false,
+        }
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        let msg = e.to_string();
+
+        // the position is kept separately, no need to have it twice
+        let message = msg
+            .rsplit_once(" at line ")
+            .map_or(msg.as_str(), |(m, _)| m)
+            .to_string();
+
+        Self::Parse {
+            line: e.line(),
+            column: e.column(),
+            message,
+        }
+    }
+}
+
+impl From<reqwest::Error> for LoadError {
+    fn from(e: reqwest::Error) -> Self {
+        match e.status() {
+            Some(status) => Self::Status {
+                status: status.as_u16(),
+                message: status.canonical_reason().unwrap_or_default().to_string(),
+            },
+            None => Self::Network(e.to_string()),
+        }
+    }
+}
+
+impl FromServerFnError for LoadError {
+    type Encoder = JsonEncoding;
+
+    fn from_server_fn_error(value: ServerFnErrorErr) -> Self {
+        match value {
+            ServerFnErrorErr::Request(e) => Self::Network(e),
+            other => Self::Server(other.to_string()),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    sync::Arc,
+};
+
+use codee::string::JsonSerdeCodec;
+use leptos::prelude::*;
+use leptos_use::{
+    storage::{UseStorageOptions, use_session_storage_with_options},
+    utils::FilterOptions,
+};
+
+use super::{DedupValue, NodePath};
+
+const STORAGE_KEY: &str = "raw-explorer-expanded";
+
+/// Most rows a single expand action may add, beyond that the page gets unresponsive.
+pub const MAX_EXPANDED_ROWS: usize = 5000;
+
+/// Expanded paths as a tree of their segments, a lot smaller than listing every path.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct OpenPaths {
+    #[serde(rename = "o", default, skip_serializing_if = "std::ops::Not::not")]
+    open: bool,
+    #[serde(rename = "c", default, skip_serializing_if = "BTreeMap::is_empty")]
+    children: BTreeMap<Arc<str>, OpenPaths>,
+}
+
+impl OpenPaths {
+    fn from_set(set: &HashSet<NodePath>) -> Self {
+        let mut root = Self::default();
+        for path in set {
+            let node = path.segments().iter().fold(&mut root, |node, s| {
+                node.children.entry(s.clone()).or_default()
+            });
+            node.open = true;
+        }
+
+        root
+    }
+
+    fn collect(&self, path: &NodePath, set: &mut HashSet<NodePath>) {
+        if self.open {
+            set.insert(path.clone());
+        }
+
+        for (key, child) in &self.children {
+            child.collect(&path.child(key.clone()), set);
+        }
+    }
+}
+
+/// Remembers which nodes of the explorer are expanded, keyed by path.
+///
+/// Provided by the [`App`](crate::app::App) so the state carries over to other variants
+/// with the same paths, it is kept in the session storage to survive reloads and
+/// back / forward navigation.
+#[derive(Debug, Clone, Copy)]
+pub struct ExpansionStore {
+    open: StoredValue<HashSet<NodePath>>,
+    /// Bumped by bulk actions so rendered nodes pick up their new state.
+    revision: RwSignal<usize>,
+    persist: WriteSignal<OpenPaths>,
+}
+
+impl ExpansionStore {
+    pub fn new() -> Self {
+        let (stored, persist, _) = use_session_storage_with_options::<OpenPaths, JsonSerdeCodec>(
+            STORAGE_KEY,
+            UseStorageOptions::default().filter(FilterOptions::debounce(250.0)),
+        );
+
+        let store = Self {
+            open: StoredValue::new(HashSet::new()),
+            revision: RwSignal::new(0),
+            persist,
+        };
+
+        // the storage is only readable in the browser, so restore once mounted
+        Effect::new(move |_| {
+            let mut open = HashSet::new();
+            stored.with_untracked(|s| s.collect(&NodePath::root(), &mut open));
+            if !open.is_empty() {
+                store.open.update_value(|o| o.extend(open));
+                store.revision.update(|r| *r += 1);
+            }
+        });
+
+        store
+    }
+
+    pub fn is_open(&self, path: &NodePath) -> bool {
+        self.open.with_value(|open| open.contains(path))
+    }
+
+    pub fn set_open(&self, path: NodePath, open: bool) {
+        let changed = self
+            .open
+            .try_update_value(|expanded| {
+                if open {
+                    expanded.insert(path)
+                } else {
+                    expanded.remove(&path)
+                }
+            })
+            .unwrap_or_default();
+
+        if changed {
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        self.persist.set(self.open.with_value(OpenPaths::from_set));
+    }
+
+    /// Subscribes to bulk changes.
+    pub fn track(&self) {
+        self.revision.track();
+    }
+
+    /// Opens `path` and the containers below it up to `depth` levels deep.
+    ///
+    /// Goes breadth first and stops at [`MAX_EXPANDED_ROWS`] so the upper levels are complete,
+    /// returns `false` if the limit was hit.
+    pub fn expand(&self, path: &NodePath, val: &DedupValue, depth: usize) -> bool {
+        let mut rows = 0;
+        let mut queue = VecDeque::from([(path.clone(), val.clone(), 0)]);
+        let mut complete = true;
+
+        self.open.update_value(|open| {
+            while let Some((path, val, level)) = queue.pop_front() {
+                let children = match &val {
+                    DedupValue::Array(arr) => arr
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, v)| (path.child(idx.to_string().into()), v.clone()))
+                        .collect::<Vec<_>>(),
+                    DedupValue::Object(obj) => obj
+                        .iter()
+                        .map(|(k, v)| (path.child(k.clone()), v.clone()))
+                        .collect(),
+                    _ => continue,
+                };
+
+                if rows + children.len() > MAX_EXPANDED_ROWS {
+                    complete = false;
+                    break;
+                }
+
+                rows += children.len();
+                open.insert(path);
+
+                if level + 1 < depth {
+                    queue.extend(children.into_iter().map(|(p, v)| (p, v, level + 1)));
+                }
+            }
+        });
+
+        self.save();
+        self.revision.update(|r| *r += 1);
+        complete
+    }
+
+    /// Opens `path` and everything leading to it.
+    pub fn reveal(&self, path: &NodePath) {
+        let changed = self
+            .open
+            .try_update_value(|open| {
+                let before = open.len();
+                open.extend(path.ancestors());
+                open.len() != before
+            })
+            .unwrap_or_default();
+
+        if changed {
+            self.save();
+            self.revision.update(|r| *r += 1);
+        }
+    }
+
+    /// Closes every node below `path`, `path` itself stays as it is.
+    pub fn collapse_below(&self, path: &NodePath) {
+        self.open
+            .update_value(|open| open.retain(|p| p == path || !path.contains(p)));
+        self.save();
+        self.revision.update(|r| *r += 1);
+    }
+}
+
+impl Default for ExpansionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,32 +1,31 @@
-use super::DedupValue;
+use super::LoadError;
 
-pub async fn fetch_data<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, String> {
+pub async fn fetch_data<T: serde::de::DeserializeOwned>(
+    url: &str,
+    timeout: std::time::Duration,
+) -> Result<T, LoadError> {
     let resp = reqwest::Client::new()
         .get(url)
+        .timeout(timeout)
         .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     let status = resp.status();
     if !status.is_success() {
-        let msg = resp.text().await.map_err(|e| e.to_string())?;
-
-        if msg.is_empty() {
-            return Err(format!("Request failed with status: {status}"));
+        let msg = resp.text().await?;
+        let message = if msg.is_empty() {
+            status.canonical_reason().unwrap_or_default().to_string()
         } else {
-            return Err(format!("Request failed ({status}): {msg}"));
-        }
-    }
-
-    let json = resp.json::<T>().await.map_err(|e| e.to_string())?;
-    Ok(json)
-}
+            msg
+        };
 
-pub async fn fetch_from_resolver<T: serde::de::DeserializeOwned>(uri: &str) -> Result<T, String> {
-    fetch_data(&format!("https://modname_resolver.bpbin.com/{uri}")).await
-}
+        return Err(LoadError::Status {
+            status: status.as_u16(),
+            message,
+        });
+    }
 
-pub async fn get_dump(variant: String) -> Result<DedupValue, String> {
-    let res = fetch_from_resolver(&format!("raw/{variant}")).await?;
-    Ok(res)
+    // parsing the raw bytes keeps the position of syntax errors
+    let raw = resp.bytes().await?;
+    Ok(serde_json::from_slice(&raw)?)
 }
@@ -0,0 +1,114 @@
+/// Smallest edit distance between `needle` and any substring of `haystack`.
+fn substring_distance(needle: &[char], haystack: &[char]) -> usize {
+    // row over the needle, the haystack may start anywhere so the first column stays 0
+    let mut row = (0..=needle.len()).collect::<Vec<_>>();
+    let mut best = row[needle.len()];
+
+    for ch in haystack {
+        let mut diag = row[0];
+        row[0] = 0;
+
+        for (i, n) in needle.iter().enumerate() {
+            let above = row[i + 1];
+            row[i + 1] = if n == ch {
+                diag
+            } else {
+                1 + diag.min(above).min(row[i])
+            };
+            diag = above;
+        }
+
+        best = best.min(row[needle.len()]);
+    }
+
+    best
+}
+
+/// Scores how well `candidate` matches `query`, higher is better.
+///
+/// Exact, prefix and substring matches rank first, followed by candidates containing
+/// the query's chars in order and finally candidates that only match with a few typos.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query = query.trim().to_lowercase();
+    let candidate = candidate.to_lowercase();
+
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let len_diff = candidate.len().abs_diff(query.len()).min(99) as u32;
+
+    if candidate == query {
+        return Some(1000);
+    }
+
+    if candidate.starts_with(&query) {
+        return Some(900 - len_diff);
+    }
+
+    if let Some(pos) = candidate.find(&query) {
+        return Some(800 - (pos as u32).min(99) - len_diff);
+    }
+
+    let q = query.chars().collect::<Vec<_>>();
+    let c = candidate.chars().collect::<Vec<_>>();
+
+    // all query chars in order, rewarding consecutive runs
+    let mut qi = 0;
+    let mut gaps = 0u32;
+    let mut last = None;
+    for (ci, ch) in c.iter().enumerate() {
+        if qi < q.len() && *ch == q[qi] {
+            if last.is_some_and(|l| l + 1 != ci) {
+                gaps += 1;
+            }
+            last = Some(ci);
+            qi += 1;
+        }
+    }
+
+    if qi == q.len() {
+        return Some(600 - (gaps * 10).min(150) - len_diff);
+    }
+
+    let max_typos = q.len() / 4 + 1;
+    let typos = substring_distance(&q, &c);
+    (typos <= max_typos).then(|| {
+        400u32
+            .saturating_sub(typos as u32 * 50)
+            .saturating_sub(len_diff)
+    })
+}
+
+/// Ranks `items` by how well their key matches `query`, best match first.
+pub fn fuzzy_rank<T>(
+    query: &str,
+    items: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> &str,
+) -> Vec<T> {
+    let mut ranked = items
+        .into_iter()
+        .filter_map(|item| Some((fuzzy_score(query, key(&item))?, item)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| key(a).len().cmp(&key(b).len()))
+            .then_with(|| key(a).cmp(key(b)))
+    });
+
+    ranked.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Picks the names closest to `query`, best match first.
+pub fn suggestions<'a>(
+    query: &str,
+    names: impl IntoIterator<Item = &'a str>,
+    max: usize,
+) -> Vec<&'a str> {
+    let mut ranked = fuzzy_rank(query, names, |name| *name);
+    ranked.dedup();
+    ranked.truncate(max);
+    ranked
+}
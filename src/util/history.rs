@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use codee::string::JsonSerdeCodec;
+use leptos::prelude::*;
+use leptos_router::location::Url;
+use leptos_use::storage::{UseStorageOptions, use_local_storage_with_options};
+
+use super::NodePath;
+
+const STORAGE_KEY: &str = "raw-explorer-history";
+
+/// How many recent variants and recent paths are remembered each.
+const MAX_RECENT: usize = 8;
+
+/// A place in the explorer worth coming back to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Visit {
+    Variant {
+        variant: String,
+        version: Option<String>,
+    },
+    Path {
+        variant: String,
+        path: NodePath,
+    },
+}
+
+impl Visit {
+    pub fn href(&self) -> String {
+        match self {
+            Self::Variant {
+                variant,
+                version: Some(version),
+            } => format!("/e/{variant}/{version}"),
+            Self::Variant {
+                variant,
+                version: None,
+            } => format!("/e/{variant}"),
+            Self::Path { variant, path } => {
+                format!("/e/{variant}?path={}", Url::escape(&path.to_string()))
+            }
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Variant {
+                variant,
+                version: Some(version),
+            } => format!("{variant} {version}"),
+            Self::Variant {
+                variant,
+                version: None,
+            } => variant.clone(),
+            Self::Path { variant, path } => {
+                let segments = path
+                    .segments()
+                    .iter()
+                    .map(AsRef::as_ref)
+                    .collect::<Vec<&str>>();
+                format!("{variant}: {}", segments.join("."))
+            }
+        }
+    }
+
+    /// Checks if `other` is the same visit or a node below this one.
+    fn leads_to(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Path { variant, path },
+                Self::Path {
+                    variant: other_variant,
+                    path: other_path,
+                },
+            ) => variant == other_variant && path.contains(other_path),
+            _ => self == other,
+        }
+    }
+}
+
+/// Recently opened variants and paths plus the pinned favorites.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct History {
+    /// Most recent first.
+    pub recent: Vec<Visit>,
+    pub pinned: Vec<Visit>,
+    /// Number of prototypes in the latest dump of each variant opened, for the mod search.
+    pub prototypes: BTreeMap<String, usize>,
+}
+
+impl History {
+    pub fn visit(&mut self, visit: Visit) {
+        // opening a deeper node supersedes the nodes leading to it
+        self.recent.retain(|v| !v.leads_to(&visit));
+        self.recent.insert(0, visit);
+
+        let (mut variants, mut paths) = (0, 0);
+        self.recent.retain(|v| {
+            let count = match v {
+                Visit::Variant { .. } => &mut variants,
+                Visit::Path { .. } => &mut paths,
+            };
+            *count += 1;
+            *count <= MAX_RECENT
+        });
+    }
+
+    pub fn is_pinned(&self, visit: &Visit) -> bool {
+        self.pinned.contains(visit)
+    }
+
+    pub fn toggle_pin(&mut self, visit: Visit) {
+        if self.is_pinned(&visit) {
+            self.pinned.retain(|v| *v != visit);
+        } else {
+            self.pinned.push(visit);
+        }
+    }
+
+    /// Recent variants that are not pinned already.
+    pub fn recent_variants(&self) -> impl Iterator<Item = &Visit> {
+        self.recent
+            .iter()
+            .filter(|v| matches!(v, Visit::Variant { .. }) && !self.is_pinned(v))
+    }
+
+    /// Recent paths that are not pinned already.
+    pub fn recent_paths(&self) -> impl Iterator<Item = &Visit> {
+        self.recent
+            .iter()
+            .filter(|v| matches!(v, Visit::Path { .. }) && !self.is_pinned(v))
+    }
+}
+
+/// The [`History`] persisted in local storage, provided by the [`App`](crate::app::App).
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryStore {
+    history: Signal<History>,
+    set_history: WriteSignal<History>,
+}
+
+impl HistoryStore {
+    pub fn new() -> Self {
+        // the server can't know the stored history, reading it during hydration would mismatch
+        let (history, set_history, _) = use_local_storage_with_options::<History, JsonSerdeCodec>(
+            STORAGE_KEY,
+            UseStorageOptions::default().delay_during_hydration(true),
+        );
+
+        Self {
+            history,
+            set_history,
+        }
+    }
+
+    pub fn get(&self) -> Signal<History> {
+        self.history
+    }
+
+    pub fn visit(&self, visit: Visit) {
+        self.set_history.update(|h| h.visit(visit));
+    }
+
+    pub fn toggle_pin(&self, visit: Visit) {
+        self.set_history.update(|h| h.toggle_pin(visit));
+    }
+
+    /// Remembers how many prototypes the latest dump of `variant` has.
+    pub fn counted(&self, variant: String, prototypes: usize) {
+        let known = self
+            .history
+            .with_untracked(|h| h.prototypes.get(&variant) == Some(&prototypes));
+        if !known {
+            self.set_history.update(|h| {
+                h.prototypes.insert(variant, prototypes);
+            });
+        }
+    }
+}
+
+impl Default for HistoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn use_history() -> HistoryStore {
+    expect_context()
+}
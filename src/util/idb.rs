@@ -0,0 +1,117 @@
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+const DB_NAME: &str = "raw-explorer";
+const STORE: &str = "cache";
+
+/// A cached download together with the `ETag` (or another version) it was valid for.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub etag: Option<String>,
+    pub data: js_sys::Uint8Array,
+}
+
+/// Key value store in the browser's IndexedDB, works in the page as well as in workers.
+///
+/// Failures are treated like cache misses, a cache that can't be used is only slower.
+#[derive(Debug, Clone)]
+pub struct IdbCache {
+    db: IdbDatabase,
+}
+
+impl IdbCache {
+    pub async fn open() -> Option<Self> {
+        let factory = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("indexedDB"))
+            .ok()?
+            .dyn_into::<web_sys::IdbFactory>()
+            .ok()?;
+        let open = factory.open_with_u32(DB_NAME, 1).ok()?;
+
+        let upgrade = Closure::<dyn Fn(web_sys::Event)>::new({
+            let open = open.clone();
+            move |_: web_sys::Event| {
+                if let Ok(db) = open.result() {
+                    let _ = db
+                        .unchecked_into::<IdbDatabase>()
+                        .create_object_store(STORE);
+                }
+            }
+        });
+        open.set_onupgradeneeded(Some(upgrade.as_ref().unchecked_ref()));
+
+        let db = finished(&open).await?.dyn_into().ok()?;
+        Some(Self { db })
+    }
+
+    fn store(&self, mode: IdbTransactionMode) -> Option<IdbObjectStore> {
+        self.db
+            .transaction_with_str_and_mode(STORE, mode)
+            .ok()?
+            .object_store(STORE)
+            .ok()
+    }
+
+    pub async fn get(&self, key: &str) -> Option<CacheEntry> {
+        let req = self
+            .store(IdbTransactionMode::Readonly)?
+            .get(&JsValue::from_str(key))
+            .ok()?;
+        let entry = finished(&req).await?;
+
+        let field = |name: &str| js_sys::Reflect::get(&entry, &JsValue::from_str(name)).ok();
+        Some(CacheEntry {
+            etag: field("etag")?.as_string(),
+            data: field("data")?.dyn_into().ok()?,
+        })
+    }
+
+    pub async fn put(&self, key: &str, entry: &CacheEntry) {
+        let obj = js_sys::Object::new();
+        let etag = entry
+            .etag
+            .as_deref()
+            .map_or(JsValue::NULL, JsValue::from_str);
+        let _ = js_sys::Reflect::set(&obj, &JsValue::from_str("etag"), &etag);
+        let _ = js_sys::Reflect::set(&obj, &JsValue::from_str("data"), &entry.data);
+
+        let Some(store) = self.store(IdbTransactionMode::Readwrite) else {
+            return;
+        };
+        if let Ok(req) = store.put_with_key(&obj, &JsValue::from_str(key)) {
+            finished(&req).await;
+        }
+    }
+
+    pub async fn delete(&self, key: &str) {
+        let Some(store) = self.store(IdbTransactionMode::Readwrite) else {
+            return;
+        };
+        if let Ok(req) = store.delete(&JsValue::from_str(key)) {
+            finished(&req).await;
+        }
+    }
+}
+
+/// Waits for a request and returns its result, `None` if it failed.
+async fn finished(req: &IdbRequest) -> Option<JsValue> {
+    let mut handlers = None;
+    let done = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_success = Closure::<dyn Fn(web_sys::Event)>::new(move |_: web_sys::Event| {
+            let _ = resolve.call0(&JsValue::NULL);
+        });
+        let on_error = Closure::<dyn Fn(web_sys::Event)>::new(move |_: web_sys::Event| {
+            let _ = reject.call0(&JsValue::NULL);
+        });
+
+        req.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+        req.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        handlers = Some((on_success, on_error));
+    });
+
+    let res = JsFuture::from(done).await;
+    drop(handlers);
+    res.ok()?;
+
+    req.result().ok().filter(|val| !val.is_undefined())
+}
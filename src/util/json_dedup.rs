@@ -5,6 +5,8 @@ use std::{
     sync::Arc,
 };
 
+use serde::de::{self, DeserializeSeed, Visitor};
+
 #[derive(Debug, Default, serde::Serialize)]
 pub struct Map<V> {
     #[serde(flatten)]
@@ -62,65 +64,264 @@ pub enum DedupValue {
     Object(Map<Self>),
 }
 
+/// Deduplicates strings and whole subtrees while a dump is built.
+///
+/// Subtrees are interned bottom up, so by the time a container is looked up its children
+/// are already shared and it can be compared by their pointers instead of their contents.
+#[derive(Default)]
+pub struct Interner {
+    strings: HashSet<Arc<str>>,
+    subtrees: HashSet<Subtree>,
+}
+
+impl Interner {
+    pub fn string(&mut self, s: &str) -> Arc<str> {
+        if let Some(known) = self.strings.get(s) {
+            return known.clone();
+        }
+
+        let s = Arc::<str>::from(s);
+        self.strings.insert(s.clone());
+        s
+    }
+
+    /// Returns the already known equal container if there is one, scalars are returned as is.
+    pub fn subtree(&mut self, val: DedupValue) -> DedupValue {
+        if !matches!(val, DedupValue::Array(_) | DedupValue::Object(_)) {
+            return val;
+        }
+
+        let val = Subtree(val);
+        if let Some(known) = self.subtrees.get(&val) {
+            return known.0.clone();
+        }
+
+        self.subtrees.insert(Subtree(val.0.clone()));
+        val.0
+    }
+}
+
+/// Container with interned children, compared and hashed only one level deep.
+struct Subtree(DedupValue);
+
+impl Subtree {
+    fn hash_child<H: std::hash::Hasher>(val: &DedupValue, state: &mut H) {
+        match val {
+            DedupValue::Array(a) => Arc::as_ptr(a).cast::<()>().hash(state),
+            DedupValue::Object(o) => Arc::as_ptr(&o.map).hash(state),
+            scalar => scalar.hash(state),
+        }
+    }
+}
+
+impl PartialEq for Subtree {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (DedupValue::Array(a), DedupValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a.ptr_eq(b))
+            }
+            (DedupValue::Object(a), DedupValue::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|((ka, va), (kb, vb))| ka == kb && va.ptr_eq(vb))
+            }
+            (a, b) => a.ptr_eq(b),
+        }
+    }
+}
+
+impl Eq for Subtree {}
+
+impl Hash for Subtree {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(&self.0).hash(state);
+        match &self.0 {
+            DedupValue::Array(a) => {
+                a.len().hash(state);
+                a.iter().for_each(|v| Self::hash_child(v, state));
+            }
+            DedupValue::Object(o) => {
+                o.len().hash(state);
+                for (k, v) in o.iter() {
+                    k.hash(state);
+                    Self::hash_child(v, state);
+                }
+            }
+            scalar => scalar.hash(state),
+        }
+    }
+}
+
 impl DedupValue {
-    fn dedup_helper(value: serde_json::Value, known: &mut HashSet<Arc<str>>) -> Self {
-        match value {
+    fn dedup_helper(value: serde_json::Value, interner: &mut Interner) -> Self {
+        let val = match value {
             serde_json::Value::Null => Self::Null,
             serde_json::Value::Bool(b) => Self::Bool(b),
             serde_json::Value::Number(n) => Self::Number(n),
-            serde_json::Value::String(s) => {
-                let s = Arc::from(s);
-
-                #[allow(clippy::single_match_else, clippy::option_if_let_else)]
-                let s = match known.get(&s) {
-                    Some(exists) => exists.clone(),
-                    None => {
-                        known.insert(s.clone());
-                        s
-                    }
-                };
-
-                Self::String(s)
-            }
+            serde_json::Value::String(s) => Self::String(interner.string(&s)),
             serde_json::Value::Array(a) => {
                 let a = a
                     .into_iter()
-                    .map(|v| Self::dedup_helper(v, known))
+                    .map(|v| Self::dedup_helper(v, interner))
                     .collect();
                 Self::Array(a)
             }
             serde_json::Value::Object(o) => {
                 let o = o
                     .into_iter()
-                    .map(|(k, v)| {
-                        let k = Arc::from(k);
-
-                        #[allow(clippy::single_match_else, clippy::option_if_let_else)]
-                        let k = match known.get(&k) {
-                            Some(exists) => exists.clone(),
-                            None => {
-                                known.insert(k.clone());
-                                k
-                            }
-                        };
-
-                        (k, Self::dedup_helper(v, known))
-                    })
+                    .map(|(k, v)| (interner.string(&k), Self::dedup_helper(v, interner)))
                     .collect();
                 Self::Object(Map { map: Arc::new(o) })
             }
-        }
+        };
+
+        interner.subtree(val)
     }
 }
 
 impl From<serde_json::Value> for DedupValue {
     fn from(value: serde_json::Value) -> Self {
-        Self::dedup_helper(value, &mut HashSet::default())
+        Self::dedup_helper(value, &mut Interner::default())
     }
 }
 
 impl<'de> serde::Deserialize<'de> for DedupValue {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        serde_json::Value::deserialize(deserializer).map(Self::from)
+        ValueSeed(&mut Interner::default()).deserialize(deserializer)
+    }
+}
+
+/// Builds a [`DedupValue`] straight from the deserializer, without an intermediate
+/// [`serde_json::Value`], so strings, keys and subtrees are interned as they are read.
+struct ValueSeed<'a>(&'a mut Interner);
+
+impl<'de> DeserializeSeed<'de> for ValueSeed<'_> {
+    type Value = DedupValue;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ValueSeed<'_> {
+    type Value = DedupValue;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("any valid JSON value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(DedupValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(DedupValue::Null)
+    }
+
+    fn visit_some<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(DedupValue::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(DedupValue::Number(v.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(DedupValue::Number(v.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        // same as serde_json::Value: non finite floats become null
+        Ok(serde_json::Number::from_f64(v).map_or(DedupValue::Null, DedupValue::Number))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(DedupValue::String(self.0.string(v)))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(item) = seq.next_element_seed(ValueSeed(&mut *self.0))? {
+            items.push(item);
+        }
+
+        Ok(self.0.subtree(DedupValue::Array(items.into())))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = BTreeMap::new();
+        while let Some(key) = map.next_key_seed(KeySeed(&mut *self.0))? {
+            let val = map.next_value_seed(ValueSeed(&mut *self.0))?;
+            entries.insert(key, val);
+        }
+
+        let obj = DedupValue::Object(Map {
+            map: Arc::new(entries),
+        });
+        Ok(self.0.subtree(obj))
+    }
+}
+
+/// Interns object keys without allocating them first.
+struct KeySeed<'a>(&'a mut Interner);
+
+impl<'de> DeserializeSeed<'de> for KeySeed<'_> {
+    type Value = Arc<str>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for KeySeed<'_> {
+    type Value = Arc<str>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a string key")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.0.string(v))
+    }
+}
+
+impl DedupValue {
+    /// Looks up a direct child by object key or array index.
+    pub fn child(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(o) => o.get(key),
+            Self::Array(a) => a.get(key.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+
+    pub fn get_path(&self, path: &super::NodePath) -> Option<&Self> {
+        path.segments()
+            .iter()
+            .try_fold(self, |val, key| val.child(key))
+    }
+
+    /// Cheap identity check: containers are only compared by their shared allocation.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Array(a), Self::Array(b)) => Arc::ptr_eq(a, b),
+            (Self::Object(a), Self::Object(b)) => Arc::ptr_eq(&a.map, &b.map),
+            (Self::Array(_) | Self::Object(_), _) | (_, Self::Array(_) | Self::Object(_)) => false,
+            (a, b) => a == b,
+        }
     }
 }
@@ -0,0 +1,109 @@
+use leptos::prelude::*;
+
+/// Server sent events endpoint announcing changed local dumps by their variant name.
+pub const LOCAL_DUMP_EVENTS: &str = "/events/local-dumps";
+
+/// Broadcasts the variant names of local dumps whose file changed.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone)]
+pub struct LocalDumpChanges(tokio::sync::broadcast::Sender<String>);
+
+#[cfg(feature = "ssr")]
+impl LocalDumpChanges {
+    /// Polls the modification times of all local dumps.
+    ///
+    /// A change is only announced once the file stopped changing for one `interval`
+    /// so explorers don't reload a dump that is still being written.
+    pub fn watch(local: super::LocalDumps, interval: std::time::Duration) -> Self {
+        let (tx, _) = tokio::sync::broadcast::channel(16);
+        let sender = tx.clone();
+
+        tokio::spawn(async move {
+            let mut known = std::collections::HashMap::new();
+            let mut first_scan = true;
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+
+                let Ok(modified) = local.modified().await else {
+                    continue;
+                };
+
+                for (variant, time) in modified {
+                    let pending = match known.get(&variant) {
+                        Some((known_time, _)) if *known_time != time => true,
+                        Some((_, true)) => {
+                            // nobody listening is fine
+                            let _ = sender.send(variant.clone());
+                            false
+                        }
+                        Some((_, false)) => continue,
+                        None => !first_scan,
+                    };
+
+                    known.insert(variant, (time, pending));
+                }
+
+                first_scan = false;
+            }
+        });
+
+        Self(tx)
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub async fn local_dump_events(
+    axum::Extension(changes): axum::Extension<LocalDumpChanges>,
+) -> axum::response::sse::Sse<
+    impl futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>>,
+> {
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use tokio::sync::broadcast::error::RecvError;
+
+    let stream = futures::stream::unfold(changes.0.subscribe(), |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(variant) => return Some((Ok(Event::default().data(variant)), rx)),
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Calls `on_change` with the variant name whenever a local dump changes on the server
+/// while `enabled` returns true.
+pub fn use_local_dump_changes(
+    enabled: impl Fn() -> bool + 'static,
+    on_change: impl Fn(String) + Clone + 'static,
+) {
+    use wasm_bindgen::{JsCast, closure::Closure};
+
+    Effect::new(move |_| {
+        if !enabled() {
+            return;
+        }
+
+        let Ok(source) = web_sys::EventSource::new(LOCAL_DUMP_EVENTS) else {
+            return;
+        };
+
+        let on_change = on_change.clone();
+        let handler =
+            Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |ev: web_sys::MessageEvent| {
+                if let Some(variant) = ev.data().as_string() {
+                    on_change(variant);
+                }
+            });
+        source.set_onmessage(Some(handler.as_ref().unchecked_ref()));
+
+        let source = StoredValue::new_local((source, handler));
+        on_cleanup(move || {
+            source.try_with_value(|(source, _)| source.close());
+        });
+    });
+}
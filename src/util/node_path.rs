@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+/// Location of a node inside a dump as the keys / indices leading to it from the root.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct NodePath(Arc<[Arc<str>]>);
+
+impl NodePath {
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn segments(&self) -> &[Arc<str>] {
+        &self.0
+    }
+
+    pub fn last(&self) -> Option<&Arc<str>> {
+        self.0.last()
+    }
+
+    pub fn child(&self, key: Arc<str>) -> Self {
+        self.0.iter().cloned().chain(std::iter::once(key)).collect()
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.0.split_last()?;
+        Some(parent.iter().cloned().collect())
+    }
+
+    /// Parses the [`Display`](std::fmt::Display) form, e.g. `data.raw.recipe.iron-plate`.
+    ///
+    /// Keys containing dots can't be told apart from nested keys and end up split.
+    pub fn parse(path: &str) -> Self {
+        let path = path.trim();
+        let rest = match path.strip_prefix("data.raw") {
+            Some("") => "",
+            Some(rest) => rest.strip_prefix('.').unwrap_or(path),
+            None => path,
+        };
+
+        rest.split('.')
+            .filter(|s| !s.is_empty())
+            .map(Arc::from)
+            .collect()
+    }
+
+    /// `self` and all of its ancestors, starting at the root.
+    pub fn ancestors(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..=self.0.len()).map(|len| self.0[..len].iter().cloned().collect())
+    }
+
+    /// Checks if `self` is `other` or one of its ancestors.
+    pub fn contains(&self, other: &Self) -> bool {
+        other.0.starts_with(&self.0)
+    }
+}
+
+impl FromIterator<Arc<str>> for NodePath {
+    fn from_iter<T: IntoIterator<Item = Arc<str>>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl std::fmt::Display for NodePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "data.raw")?;
+        for segment in self.0.iter() {
+            write!(f, ".{segment}")?;
+        }
+
+        Ok(())
+    }
+}
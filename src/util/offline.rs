@@ -0,0 +1,25 @@
+/// Service worker caching the app itself, dumps and docs are kept in IndexedDB by the app.
+pub const SERVICE_WORKER: &str = "/sw.js";
+
+/// Web app manifest making the explorer installable.
+pub const WEB_MANIFEST: &str = "/manifest.webmanifest";
+
+/// Registers the [`SERVICE_WORKER`] so the explorer keeps working without a network.
+pub fn register_service_worker() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    // missing on plain http and in browsers without service workers, the app just isn't
+    // available offline there
+    let navigator = window.navigator();
+    if !js_sys::Reflect::has(&navigator, &"serviceWorker".into()).unwrap_or(false) {
+        return;
+    }
+
+    let registration = navigator.service_worker().register(SERVICE_WORKER);
+    leptos::task::spawn_local(async move {
+        // registering can still be refused, e.g. for a script the browser considers insecure
+        let _ = wasm_bindgen_futures::JsFuture::from(registration).await;
+    });
+}
@@ -0,0 +1,176 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use super::{DedupValue, TypeHelper};
+
+/// Sizes and counts of a whole dump, shown on the overview of a variant.
+#[derive(Debug, Clone)]
+pub struct DumpOverview {
+    /// Largest types first.
+    pub types: Vec<TypeOverview>,
+    pub prototypes: usize,
+    pub size: usize,
+    /// Distinct keys and string values, each of them is only stored once.
+    pub distinct_strings: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeOverview {
+    pub name: Arc<str>,
+    pub size: usize,
+    /// Prototypes of this type with their size, largest first.
+    pub prototypes: Vec<(Arc<str>, usize)>,
+}
+
+/// Object keys of the dump that the docs have a type for.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocsCoverage {
+    pub typed: usize,
+    pub total: usize,
+}
+
+impl DumpOverview {
+    pub fn new(data: &DedupValue) -> Self {
+        // one pass over the whole dump, the sizes of types and prototypes are looked up after
+        let mut sizes = JsonSizes::default();
+        let size = sizes.of(data);
+
+        let mut types = Vec::new();
+        if let DedupValue::Object(obj) = data {
+            for (name, protos) in obj.iter() {
+                let mut prototypes = match protos {
+                    DedupValue::Object(protos) => protos
+                        .iter()
+                        .map(|(name, proto)| (name.clone(), sizes.of(proto)))
+                        .collect::<Vec<_>>(),
+                    _ => Vec::new(),
+                };
+                prototypes.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+                types.push(TypeOverview {
+                    name: name.clone(),
+                    size: sizes.of(protos),
+                    prototypes,
+                });
+            }
+        }
+        types.sort_unstable_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        Self {
+            prototypes: types.iter().map(|t| t.prototypes.len()).sum(),
+            size,
+            distinct_strings: sizes.distinct_strings(),
+            types,
+        }
+    }
+}
+
+/// Number of prototypes in a dump, without looking into any of them.
+pub fn prototype_count(data: &DedupValue) -> usize {
+    match data {
+        DedupValue::Object(types) => types
+            .values()
+            .map(|protos| match protos {
+                DedupValue::Object(protos) => protos.len(),
+                _ => 0,
+            })
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Lengths of the compact JSON of values, escapes in strings are not counted.
+///
+/// Sizes of arrays and objects are kept by their allocation, subtrees shared by the
+/// parser are only measured once no matter how often they occur.
+#[derive(Debug, Default)]
+pub struct JsonSizes<'a> {
+    sizes: HashMap<*const (), usize>,
+    strings: HashSet<&'a str>,
+}
+
+impl<'a> JsonSizes<'a> {
+    pub fn of(&mut self, val: &'a DedupValue) -> usize {
+        let separators = |len: usize| len.saturating_sub(1);
+
+        let ptr = match val {
+            DedupValue::Null | DedupValue::Bool(true) => return 4,
+            DedupValue::Bool(false) => return 5,
+            DedupValue::Number(n) => return n.to_string().len(),
+            DedupValue::String(s) => {
+                self.strings.insert(s);
+                return s.len() + 2;
+            }
+            DedupValue::Array(arr) => Arc::as_ptr(arr).cast::<()>(),
+            DedupValue::Object(obj) => std::ptr::from_ref(&**obj).cast::<()>(),
+        };
+
+        if let Some(&size) = self.sizes.get(&ptr) {
+            return size;
+        }
+
+        let size = match val {
+            DedupValue::Array(arr) => {
+                2 + separators(arr.len()) + arr.iter().map(|v| self.of(v)).sum::<usize>()
+            }
+            DedupValue::Object(obj) => {
+                let mut size = 2 + separators(obj.len());
+                for (k, v) in obj.iter() {
+                    self.strings.insert(k);
+                    size += k.len() + 3 + self.of(v);
+                }
+                size
+            }
+            _ => unreachable!("scalars returned above"),
+        };
+
+        self.sizes.insert(ptr, size);
+        size
+    }
+
+    /// Distinct keys and string values of everything measured so far.
+    pub fn distinct_strings(&self) -> usize {
+        self.strings.len()
+    }
+}
+
+impl DocsCoverage {
+    /// Counts `key` of an object described by `doc` and every key below it.
+    ///
+    /// Going through a dump one prototype type at a time lets the caller pause in between.
+    pub fn add(&mut self, key: &str, val: &DedupValue, doc: &TypeHelper) {
+        let doc = doc.traverse_prop(key);
+        self.total += 1;
+        if doc.describe().is_some() {
+            self.typed += 1;
+        }
+        self.walk(val, &doc);
+    }
+
+    fn walk(&mut self, val: &DedupValue, doc: &TypeHelper) {
+        match val {
+            DedupValue::Array(arr) => {
+                for (idx, v) in arr.iter().enumerate() {
+                    self.walk(v, &doc.traverse_idx(idx, arr.len()));
+                }
+            }
+            DedupValue::Object(obj) => {
+                for (k, v) in obj.iter() {
+                    self.add(k, v, doc);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.typed as f64 / self.total as f64 * 100.0
+    }
+}
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::DedupValue;
+
+const NULL: u8 = 0;
+const FALSE: u8 = 1;
+const TRUE: u8 = 2;
+const U64: u8 = 3;
+const I64: u8 = 4;
+const F64: u8 = 5;
+const STRING: u8 = 6;
+const CONTAINER: u8 = 7;
+
+const ARRAY: u8 = 0;
+const OBJECT: u8 = 1;
+
+/// Writes a dump into a flat buffer that keeps its shared strings and subtrees shared.
+///
+/// Strings and containers go into tables once and are referenced by index, containers
+/// after all of their children, so [`unpack`] rebuilds the dump in one pass without
+/// parsing JSON or hashing anything.
+pub fn pack(root: &DedupValue) -> Vec<u8> {
+    let mut packer = Packer::default();
+    let mut root_ref = Vec::new();
+    packer.value(root, &mut root_ref);
+
+    let mut out = Vec::with_capacity(packer.strings_buf.len() + packer.nodes_buf.len() + 16);
+    put_len(&mut out, packer.strings.len());
+    out.extend(packer.strings_buf);
+    put_len(&mut out, packer.containers.len());
+    out.extend(packer.nodes_buf);
+    out.extend(root_ref);
+    out
+}
+
+/// Reads a buffer written by [`pack`], `None` if it is malformed.
+pub fn unpack(buf: &[u8]) -> Option<DedupValue> {
+    let mut reader = Reader { buf };
+
+    let string_count = reader.len()?;
+    let mut strings = Vec::with_capacity(string_count.min(buf.len()));
+    for _ in 0..string_count {
+        let len = reader.len()?;
+        let s = std::str::from_utf8(reader.bytes(len)?).ok()?;
+        strings.push(Arc::<str>::from(s));
+    }
+
+    let node_count = reader.len()?;
+    let mut nodes = Vec::with_capacity(node_count.min(buf.len()));
+    for _ in 0..node_count {
+        let node = match reader.u8()? {
+            ARRAY => {
+                let len = reader.len()?;
+                let items = (0..len)
+                    .map(|_| reader.value(&strings, &nodes))
+                    .collect::<Option<Vec<_>>>()?;
+                DedupValue::Array(items.into())
+            }
+            OBJECT => {
+                let len = reader.len()?;
+                let entries = (0..len)
+                    .map(|_| {
+                        let key = strings.get(reader.len()?)?.clone();
+                        Some((key, reader.value(&strings, &nodes)?))
+                    })
+                    .collect::<Option<_>>()?;
+                DedupValue::Object(entries)
+            }
+            _ => return None,
+        };
+        nodes.push(node);
+    }
+
+    let root = reader.value(&strings, &nodes)?;
+    reader.buf.is_empty().then_some(root)
+}
+
+fn put_len(out: &mut Vec<u8>, len: usize) {
+    let len = u32::try_from(len).expect("dump too large to pack");
+    out.extend(len.to_le_bytes());
+}
+
+#[derive(Default)]
+struct Packer<'a> {
+    strings: HashMap<&'a str, usize>,
+    strings_buf: Vec<u8>,
+    containers: HashMap<*const (), usize>,
+    nodes_buf: Vec<u8>,
+}
+
+impl<'a> Packer<'a> {
+    fn string(&mut self, s: &'a str) -> usize {
+        let next = self.strings.len();
+        *self.strings.entry(s).or_insert_with(|| {
+            put_len(&mut self.strings_buf, s.len());
+            self.strings_buf.extend(s.as_bytes());
+            next
+        })
+    }
+
+    /// Writes a reference to `val` into `out`, packing it first if it is a new container.
+    fn value(&mut self, val: &'a DedupValue, out: &mut Vec<u8>) {
+        match val {
+            DedupValue::Null => out.push(NULL),
+            DedupValue::Bool(false) => out.push(FALSE),
+            DedupValue::Bool(true) => out.push(TRUE),
+            DedupValue::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    out.push(U64);
+                    out.extend(n.to_le_bytes());
+                } else if let Some(n) = n.as_i64() {
+                    out.push(I64);
+                    out.extend(n.to_le_bytes());
+                } else {
+                    out.push(F64);
+                    out.extend(n.as_f64().unwrap_or_default().to_le_bytes());
+                }
+            }
+            DedupValue::String(s) => {
+                out.push(STRING);
+                let idx = self.string(s);
+                put_len(out, idx);
+            }
+            DedupValue::Array(_) | DedupValue::Object(_) => {
+                out.push(CONTAINER);
+                let idx = self.container(val);
+                put_len(out, idx);
+            }
+        }
+    }
+
+    fn container(&mut self, val: &'a DedupValue) -> usize {
+        let ptr = match val {
+            DedupValue::Array(a) => Arc::as_ptr(a).cast::<()>(),
+            DedupValue::Object(o) => std::ptr::from_ref(&**o).cast::<()>(),
+            _ => unreachable!("only called for containers"),
+        };
+
+        if let Some(&idx) = self.containers.get(&ptr) {
+            return idx;
+        }
+
+        // children can add nodes themselves, so this one is only appended once they are done
+        let mut node = Vec::new();
+        match val {
+            DedupValue::Array(a) => {
+                node.push(ARRAY);
+                put_len(&mut node, a.len());
+                for item in a.iter() {
+                    self.value(item, &mut node);
+                }
+            }
+            DedupValue::Object(o) => {
+                node.push(OBJECT);
+                put_len(&mut node, o.len());
+                for (key, item) in o.iter() {
+                    let key = self.string(key);
+                    put_len(&mut node, key);
+                    self.value(item, &mut node);
+                }
+            }
+            _ => unreachable!("only called for containers"),
+        }
+
+        self.nodes_buf.extend(node);
+        let idx = self.containers.len();
+        self.containers.insert(ptr, idx);
+        idx
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let (bytes, rest) = self.buf.split_at_checked(len)?;
+        self.buf = rest;
+        Some(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.bytes(N)?.try_into().ok()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.array::<1>().map(|[b]| b)
+    }
+
+    fn len(&mut self) -> Option<usize> {
+        usize::try_from(u32::from_le_bytes(self.array()?)).ok()
+    }
+
+    fn value(&mut self, strings: &[Arc<str>], nodes: &[DedupValue]) -> Option<DedupValue> {
+        let val = match self.u8()? {
+            NULL => DedupValue::Null,
+            FALSE => DedupValue::Bool(false),
+            TRUE => DedupValue::Bool(true),
+            U64 => DedupValue::Number(u64::from_le_bytes(self.array()?).into()),
+            I64 => DedupValue::Number(i64::from_le_bytes(self.array()?).into()),
+            F64 => DedupValue::Number(serde_json::Number::from_f64(f64::from_le_bytes(
+                self.array()?,
+            ))?),
+            STRING => DedupValue::String(strings.get(self.len()?)?.clone()),
+            CONTAINER => nodes.get(self.len()?)?.clone(),
+            _ => return None,
+        };
+
+        Some(val)
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::Write;
+
+use super::DedupValue;
+
+/// Longest inline rendering shown for a collapsed node.
+const MAX_INLINE: usize = 60;
+
+/// Short summary of a collapsed array / object.
+///
+/// Small values are rendered inline like `{x = 0.5, y = -1}` or `[1, 2, 3]`,
+/// larger ones show their size and the `name` / `type` fields if they have them.
+pub fn preview(val: &DedupValue) -> Option<String> {
+    let mut inline = String::new();
+    if write_inline(val, &mut inline, MAX_INLINE).is_some() {
+        return Some(inline);
+    }
+
+    match val {
+        DedupValue::Array(arr) => Some(count(arr.len(), "item")),
+        DedupValue::Object(obj) => {
+            let mut res = count(obj.len(), "key");
+            for field in ["type", "name"] {
+                if let Some(DedupValue::String(s)) = obj.get(field) {
+                    let _ = write!(res, ", {field} = \"{s}\"");
+                }
+            }
+
+            Some(res)
+        }
+        _ => None,
+    }
+}
+
+fn count(n: usize, what: &str) -> String {
+    match n {
+        1 => format!("1 {what}"),
+        n => format!("{n} {what}s"),
+    }
+}
+
+/// Appends `val` to `out`, gives up once `out` is longer than `max`.
+fn write_inline(val: &DedupValue, out: &mut String, max: usize) -> Option<()> {
+    match val {
+        DedupValue::Null => out.push_str("null"),
+        DedupValue::Bool(b) => {
+            let _ = write!(out, "{b}");
+        }
+        DedupValue::Number(n) => {
+            let _ = write!(out, "{n}");
+        }
+        DedupValue::String(s) => {
+            if s.contains('\n') {
+                return None;
+            }
+            let _ = write!(out, "\"{s}\"");
+        }
+        DedupValue::Array(arr) => {
+            out.push('[');
+            for (idx, v) in arr.iter().enumerate() {
+                if idx > 0 {
+                    out.push_str(", ");
+                }
+                write_inline(v, out, max)?;
+            }
+            out.push(']');
+        }
+        DedupValue::Object(obj) => {
+            out.push('{');
+            for (idx, (k, v)) in obj.iter().enumerate() {
+                if idx > 0 {
+                    out.push_str(", ");
+                }
+                let _ = write!(out, "{k} = ");
+                write_inline(v, out, max)?;
+            }
+            out.push('}');
+        }
+    }
+
+    (out.len() <= max).then_some(())
+}
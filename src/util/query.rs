@@ -0,0 +1,723 @@
+use std::{cmp::Ordering, fmt, sync::Arc};
+
+use super::{DedupValue, NodePath};
+
+/// Most matches a query collects before it stops walking the dump.
+pub const MAX_QUERY_RESULTS: usize = 1000;
+
+/// Parsed query like `data.raw.*.*[max_health > 1000 && resistances] | name, max_health`.
+///
+/// - the path selects nodes, `*` matches every child and `**` any number of levels
+/// - `[..]` after a segment keeps only the nodes matching the filter
+/// - the optional projection after `|` lists fields shown as table columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    segments: Vec<Segment>,
+    columns: Vec<Field>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    selector: Selector,
+    filters: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Key(Arc<str>),
+    /// `*`, every direct child.
+    Children,
+    /// `**`, the node itself and everything below it.
+    Descendants,
+}
+
+/// Relative path to a value below a matched node, e.g. `minable.result`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field(Vec<Arc<str>>);
+
+impl Field {
+    pub fn get<'a>(&self, val: &'a DedupValue) -> Option<&'a DedupValue> {
+        self.0.iter().try_fold(val, |val, key| val.child(key))
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, key) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{key}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Or(Box<Self>, Box<Self>),
+    And(Box<Self>, Box<Self>),
+    Not(Box<Self>),
+    /// The field exists and is neither `null` nor `false`.
+    Truthy(Field),
+    Compare(Field, CompareOp, Literal),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// Substring match on strings.
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(Arc<str>),
+}
+
+/// Syntax error of a [`Query`], `position` is the byte offset into the query.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message} at position {position}")]
+pub struct QueryError {
+    pub position: usize,
+    pub message: String,
+}
+
+/// Node selected by a [`Query`].
+#[derive(Debug, Clone)]
+pub struct QueryMatch {
+    pub path: NodePath,
+    pub value: DedupValue,
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        Parser { src: query, pos: 0 }.query()
+    }
+
+    /// Fields of the projection, empty if the query has none.
+    pub fn columns(&self) -> &[Field] {
+        &self.columns
+    }
+
+    /// All matching nodes in document order, `true` if more than `limit` nodes matched.
+    pub fn run(&self, root: &DedupValue, limit: usize) -> (Vec<QueryMatch>, bool) {
+        let mut matches = Vec::new();
+        let complete = walk(&self.segments, NodePath::root(), root, &mut matches, limit);
+        (matches, !complete)
+    }
+}
+
+/// Applies `segments` to `val`, returns `false` once the limit is reached.
+fn walk(
+    segments: &[Segment],
+    path: NodePath,
+    val: &DedupValue,
+    out: &mut Vec<QueryMatch>,
+    limit: usize,
+) -> bool {
+    let Some((segment, rest)) = segments.split_first() else {
+        if out.len() >= limit {
+            return false;
+        }
+        out.push(QueryMatch {
+            path,
+            value: val.clone(),
+        });
+        return true;
+    };
+
+    let keep = |val: &DedupValue| segment.filters.iter().all(|f| f.eval(val));
+
+    match &segment.selector {
+        Selector::Key(key) => match val.child(key) {
+            Some(child) if keep(child) => walk(rest, path.child(key.clone()), child, out, limit),
+            _ => true,
+        },
+        Selector::Children => children(val)
+            .filter(|(_, child)| keep(child))
+            .all(|(key, child)| walk(rest, path.child(key), child, out, limit)),
+        Selector::Descendants => {
+            if keep(val) && !walk(rest, path.clone(), val, out, limit) {
+                return false;
+            }
+
+            children(val).all(|(key, child)| walk(segments, path.child(key), child, out, limit))
+        }
+    }
+}
+
+fn children(val: &DedupValue) -> Box<dyn Iterator<Item = (Arc<str>, &DedupValue)> + '_> {
+    match val {
+        DedupValue::Array(arr) => Box::new(
+            arr.iter()
+                .enumerate()
+                .map(|(idx, v)| (Arc::from(idx.to_string()), v)),
+        ),
+        DedupValue::Object(obj) => Box::new(obj.iter().map(|(k, v)| (k.clone(), v))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+impl Expr {
+    fn eval(&self, val: &DedupValue) -> bool {
+        match self {
+            Self::Or(a, b) => a.eval(val) || b.eval(val),
+            Self::And(a, b) => a.eval(val) && b.eval(val),
+            Self::Not(e) => !e.eval(val),
+            Self::Truthy(field) => !matches!(
+                field.get(val),
+                None | Some(DedupValue::Null | DedupValue::Bool(false))
+            ),
+            Self::Compare(field, op, lit) => field.get(val).is_some_and(|v| op.eval(v, lit)),
+        }
+    }
+}
+
+impl CompareOp {
+    fn eval(self, val: &DedupValue, lit: &Literal) -> bool {
+        if self == Self::Contains {
+            return match (val, lit) {
+                (DedupValue::String(s), Literal::String(needle)) => s.contains(&**needle),
+                _ => false,
+            };
+        }
+
+        let ord = match (val, lit) {
+            (DedupValue::Null, Literal::Null) => Some(Ordering::Equal),
+            (DedupValue::Bool(a), Literal::Bool(b)) => Some(a.cmp(b)),
+            (DedupValue::Number(a), Literal::Number(b)) => {
+                a.as_f64().and_then(|a| a.partial_cmp(b))
+            }
+            (DedupValue::String(a), Literal::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        };
+
+        match self {
+            Self::Eq => ord == Some(Ordering::Equal),
+            Self::Ne => ord != Some(Ordering::Equal),
+            Self::Lt => ord == Some(Ordering::Less),
+            Self::Le => matches!(ord, Some(Ordering::Less | Ordering::Equal)),
+            Self::Gt => ord == Some(Ordering::Greater),
+            Self::Ge => matches!(ord, Some(Ordering::Greater | Ordering::Equal)),
+            Self::Contains => unreachable!(),
+        }
+    }
+}
+
+/// Recursive descent parser working directly on the query string.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, QueryError> {
+        Err(QueryError {
+            position: self.pos,
+            message: message.into(),
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.src.len() - trimmed.len();
+    }
+
+    /// Consumes `token` if the query continues with it.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), QueryError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            self.error(format!("expected `{token}`"))
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        let len = self.rest().find(|c| !f(c)).unwrap_or(self.rest().len());
+        self.pos += len;
+        &self.src[start..self.pos]
+    }
+
+    fn query(mut self) -> Result<Query, QueryError> {
+        let mut segments = Vec::new();
+
+        // the root is optional, `recipe.*` works just as well
+        let start = self.pos;
+        if self.eat("data") && self.eat(".") && self.eat("raw") && self.segment_end() {
+            self.eat(".");
+        } else {
+            self.pos = start;
+        }
+
+        self.skip_ws();
+        if !self.rest().is_empty() && !self.rest().starts_with('|') {
+            loop {
+                segments.push(self.segment()?);
+                if !self.eat(".") {
+                    break;
+                }
+            }
+        }
+
+        let mut columns = Vec::new();
+        if self.eat("|") {
+            loop {
+                columns.push(self.field()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+        }
+
+        self.skip_ws();
+        if !self.rest().is_empty() {
+            return self.error("unexpected input");
+        }
+
+        Ok(Query { segments, columns })
+    }
+
+    /// Checks that a keyword isn't just the start of a longer key.
+    fn segment_end(&self) -> bool {
+        !self.rest().starts_with(is_key_char)
+    }
+
+    fn segment(&mut self) -> Result<Segment, QueryError> {
+        let selector = if self.eat("**") {
+            Selector::Descendants
+        } else if self.eat("*") {
+            Selector::Children
+        } else {
+            Selector::Key(self.key()?)
+        };
+
+        let mut filters = Vec::new();
+        while self.eat("[") {
+            filters.push(self.or()?);
+            self.expect("]")?;
+        }
+
+        Ok(Segment { selector, filters })
+    }
+
+    fn key(&mut self) -> Result<Arc<str>, QueryError> {
+        self.skip_ws();
+        if self.rest().starts_with('"') {
+            return self.string();
+        }
+
+        let key = self.take_while(is_key_char);
+        if key.is_empty() {
+            return self.error("expected a key");
+        }
+        Ok(key.into())
+    }
+
+    fn string(&mut self) -> Result<Arc<str>, QueryError> {
+        let start = self.pos;
+        self.pos += 1;
+
+        let mut res = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += idx + 1;
+                    return Ok(res.into());
+                }
+                '\\' => match chars.next() {
+                    Some((_, c)) => res.push(c),
+                    None => break,
+                },
+                c => res.push(c),
+            }
+        }
+
+        self.pos = start;
+        self.error("unterminated string")
+    }
+
+    fn field(&mut self) -> Result<Field, QueryError> {
+        let mut keys = vec![self.key()?];
+        while self.eat(".") {
+            keys.push(self.key()?);
+        }
+        Ok(Field(keys))
+    }
+
+    fn or(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.and()?;
+        while self.eat("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.unary()?;
+        while self.eat("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, QueryError> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.or()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+
+        let field = self.field()?;
+        let op = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+            ("~", CompareOp::Contains),
+        ]
+        .into_iter()
+        .find(|(token, _)| self.eat(token));
+
+        match op {
+            Some((_, op)) => Ok(Expr::Compare(field, op, self.literal()?)),
+            None => Ok(Expr::Truthy(field)),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal, QueryError> {
+        self.skip_ws();
+        if self.rest().starts_with('"') {
+            return self.string().map(Literal::String);
+        }
+
+        let start = self.pos;
+        let word = self.take_while(|c| is_key_char(c) || matches!(c, '.' | '+'));
+        let lit = match word {
+            "" => {
+                self.pos = start;
+                return self.error("expected a value");
+            }
+            "null" => Literal::Null,
+            "true" => Literal::Bool(true),
+            "false" => Literal::Bool(false),
+            // bare words are strings, `type == furnace` reads nicer than quoting every name,
+            // only those starting like a number are one, so `inf` or `nan` stay names
+            word if starts_numeric(word) => word
+                .parse()
+                .map_or_else(|_| Literal::String(word.into()), Literal::Number),
+            word => Literal::String(word.into()),
+        };
+
+        Ok(lit)
+    }
+}
+
+/// Checks for a digit after an optional sign and decimal point, e.g. `5`, `-0.5` or `.5`.
+fn starts_numeric(word: &str) -> bool {
+    let word = word.strip_prefix('-').unwrap_or(word);
+    let word = word.strip_prefix('.').unwrap_or(word);
+    word.starts_with(|c: char| c.is_ascii_digit())
+}
+
+const fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_')
+}
+
+/// Writes `key` so [`Query::parse`] reads it back as a single key.
+pub fn quote_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(is_key_char) {
+        return key.to_string();
+    }
+
+    let escaped = key.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn dump() -> DedupValue {
+        json!({
+            "furnace": {
+                "stone-furnace": {
+                    "type": "furnace",
+                    "max_health": 200,
+                    "energy_usage": "90kW",
+                    "minable": { "result": "stone-furnace" },
+                },
+                "steel-furnace": {
+                    "type": "furnace",
+                    "max_health": 300,
+                    "energy_usage": "90kW",
+                    "resistances": [{ "type": "fire", "percent": 100 }],
+                },
+            },
+            "recipe": {
+                "iron-plate": { "type": "recipe", "enabled": true, "energy_required": 3.2 },
+                "steel-plate": { "type": "recipe", "enabled": false, "energy_required": 16 },
+                "a.b": { "type": "recipe", "weird \"name\"": null },
+            },
+        })
+        .into()
+    }
+
+    fn paths(query: &str) -> Vec<String> {
+        let (matches, _) = Query::parse(query).unwrap().run(&dump(), usize::MAX);
+        matches
+            .into_iter()
+            .map(|m| m.path.to_string().replacen("data.raw.", "", 1))
+            .collect()
+    }
+
+    fn filter(src: &str) -> Expr {
+        let mut query = Query::parse(&format!("*[{src}]")).unwrap();
+        query.segments.remove(0).filters.remove(0)
+    }
+
+    fn truthy(key: &str) -> Expr {
+        Expr::Truthy(Field(vec![key.into()]))
+    }
+
+    #[test]
+    fn root_is_optional() {
+        assert_eq!(Query::parse("data.raw.recipe.*"), Query::parse("recipe.*"));
+        assert_eq!(Query::parse("data.raw"), Query::parse(""));
+        // only the exact keywords are the root
+        assert_eq!(
+            Query::parse("database.raw").unwrap().segments[0].selector,
+            Selector::Key("database".into())
+        );
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            filter("a || b && c"),
+            Expr::Or(
+                Box::new(truthy("a")),
+                Box::new(Expr::And(Box::new(truthy("b")), Box::new(truthy("c"))))
+            )
+        );
+        assert_eq!(
+            filter("!a && b"),
+            Expr::And(
+                Box::new(Expr::Not(Box::new(truthy("a")))),
+                Box::new(truthy("b"))
+            )
+        );
+        assert_eq!(
+            filter("(a || b) && c"),
+            Expr::And(
+                Box::new(Expr::Or(Box::new(truthy("a")), Box::new(truthy("b")))),
+                Box::new(truthy("c"))
+            )
+        );
+    }
+
+    #[test]
+    fn operators_and_literals() {
+        let compare = |src: &str| match filter(src) {
+            Expr::Compare(_, op, lit) => (op, lit),
+            e => panic!("{src} parsed as {e:?}"),
+        };
+
+        assert_eq!(compare("a!=1"), (CompareOp::Ne, Literal::Number(1.0)));
+        assert_eq!(compare("a <= -2.5"), (CompareOp::Le, Literal::Number(-2.5)));
+        assert_eq!(
+            compare("a >= 1e3"),
+            (CompareOp::Ge, Literal::Number(1000.0))
+        );
+        assert_eq!(compare("a < null"), (CompareOp::Lt, Literal::Null));
+        assert_eq!(compare("a > true"), (CompareOp::Gt, Literal::Bool(true)));
+        assert_eq!(
+            compare("a == stone-furnace"),
+            (CompareOp::Eq, Literal::String("stone-furnace".into()))
+        );
+        assert_eq!(
+            compare("a ~ \"kW\""),
+            (CompareOp::Contains, Literal::String("kW".into()))
+        );
+        // special floats are names
+        for word in ["inf", "-inf", "nan", "infinity", "NaN"] {
+            assert_eq!(
+                compare(&format!("a == {word}")),
+                (CompareOp::Eq, Literal::String(word.into()))
+            );
+        }
+        assert_eq!(compare("a > .5"), (CompareOp::Gt, Literal::Number(0.5)));
+        // versions are no numbers
+        assert_eq!(
+            compare("a == 2.0.10"),
+            (CompareOp::Eq, Literal::String("2.0.10".into()))
+        );
+    }
+
+    #[test]
+    fn quoted_keys_and_escapes() {
+        let query = Query::parse(r#"recipe."a.b"."weird \"name\"""#).unwrap();
+        let keys = query
+            .segments
+            .iter()
+            .map(|s| s.selector.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            [
+                Selector::Key("recipe".into()),
+                Selector::Key("a.b".into()),
+                Selector::Key("weird \"name\"".into()),
+            ]
+        );
+
+        assert_eq!(paths(r#"recipe."a.b""#), ["recipe.a.b"]);
+
+        for key in [
+            "plain-key_1",
+            "a.b",
+            "",
+            "with space",
+            r#"quote " and \ slash"#,
+            "*",
+        ] {
+            let query = Query::parse(&quote_key(key)).unwrap();
+            assert_eq!(
+                query.segments[0].selector,
+                Selector::Key(key.into()),
+                "{key}"
+            );
+        }
+    }
+
+    #[test]
+    fn compares_numbers_and_strings() {
+        assert_eq!(
+            paths("furnace.*[max_health > 250]"),
+            ["furnace.steel-furnace"]
+        );
+        assert_eq!(
+            paths("furnace.*[max_health >= 200 && max_health < 300]"),
+            ["furnace.stone-furnace"]
+        );
+        assert_eq!(
+            paths("recipe.*[energy_required == 3.2]"),
+            ["recipe.iron-plate"]
+        );
+        assert_eq!(paths("recipe.*[enabled]"), ["recipe.iron-plate"]);
+        assert_eq!(
+            paths("furnace.*[minable.result == stone-furnace]"),
+            ["furnace.stone-furnace"]
+        );
+
+        // mismatched types never compare, only `!=` holds for them
+        assert!(paths("furnace.*[type > 5]").is_empty());
+        assert!(paths("furnace.*[max_health == \"200\"]").is_empty());
+        assert!(paths("furnace.*[energy_usage ~ 90]").is_empty());
+        assert_eq!(paths("furnace.*[type != 5]").len(), 2);
+        // missing fields never compare either
+        assert!(paths("furnace.*[missing != 5]").is_empty());
+    }
+
+    #[test]
+    fn descendants_include_the_node_itself() {
+        assert_eq!(
+            paths("**[type == fire]"),
+            ["furnace.steel-furnace.resistances.0"]
+        );
+        assert_eq!(
+            paths("furnace.**[type == furnace]"),
+            ["furnace.steel-furnace", "furnace.stone-furnace",]
+        );
+        assert_eq!(
+            paths("furnace.steel-furnace.**.percent"),
+            ["furnace.steel-furnace.resistances.0.percent"]
+        );
+        assert_eq!(paths("**").len(), 28);
+    }
+
+    #[test]
+    fn projection() {
+        let query = Query::parse("furnace.* | max_health, minable.result").unwrap();
+        let columns = query
+            .columns()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["max_health", "minable.result"]);
+
+        let (matches, _) = query.run(&dump(), usize::MAX);
+        let results = matches
+            .iter()
+            .map(|m| query.columns()[1].get(&m.value).cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(results, [None, Some(json!("stone-furnace").into())]);
+
+        assert!(Query::parse("| name").unwrap().segments.is_empty());
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        let query = Query::parse("**").unwrap();
+        let (matches, truncated) = query.run(&dump(), 5);
+        assert_eq!(matches.len(), 5);
+        assert!(truncated);
+
+        let (matches, truncated) = Query::parse("recipe.*").unwrap().run(&dump(), 3);
+        assert_eq!(matches.len(), 3);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        let error = |query: &str| Query::parse(query).unwrap_err();
+
+        assert_eq!(
+            error("recipe."),
+            QueryError {
+                position: 7,
+                message: "expected a key".into(),
+            }
+        );
+        assert_eq!(error("recipe.\"open").position, 7);
+        assert_eq!(error("recipe.\"open").message, "unterminated string");
+        assert_eq!(error("recipe.*[enabled").message, "expected `]`");
+        assert_eq!(error("recipe.*[(a || b]").message, "expected `)`");
+        assert_eq!(error("recipe.*[a ==]").message, "expected a value");
+        assert_eq!(error("recipe.*[]").message, "expected a key");
+        assert_eq!(error("recipe *").message, "unexpected input");
+        assert_eq!(error("recipe | a,").message, "expected a key");
+        assert_eq!(error("recipe | a b").position, 11);
+    }
+}
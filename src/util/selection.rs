@@ -0,0 +1,55 @@
+use leptos::prelude::*;
+
+use super::NodePath;
+
+/// The node the user is working with, shown in the breadcrumb bar.
+#[derive(Debug, Clone, Copy)]
+pub struct Selection {
+    path: RwSignal<Option<NodePath>>,
+    /// Scroll the selected row into view once it is rendered.
+    scroll: StoredValue<bool>,
+}
+
+impl Selection {
+    pub fn new() -> Self {
+        Self {
+            path: RwSignal::new(None),
+            scroll: StoredValue::new(false),
+        }
+    }
+
+    pub fn get(&self) -> Option<NodePath> {
+        self.path.get()
+    }
+
+    pub fn get_untracked(&self) -> Option<NodePath> {
+        self.path.get_untracked()
+    }
+
+    pub fn is(&self, path: &NodePath) -> bool {
+        self.path.with(|p| p.as_ref() == Some(path))
+    }
+
+    pub fn select(&self, path: NodePath) {
+        self.path.set(Some(path));
+    }
+
+    /// Selects `path` and scrolls to it.
+    pub fn reveal(&self, path: NodePath) {
+        self.scroll.set_value(true);
+        self.path.set(Some(path));
+    }
+
+    /// Checks if the selected row should be scrolled into view, only once per reveal.
+    pub fn take_scroll(&self) -> bool {
+        self.scroll
+            .try_update_value(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,159 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::{DedupValue, NodePath, Query, QueryMatch, quote_key};
+
+/// Number of bars in the histogram of numeric values.
+pub const HISTOGRAM_BINS: usize = 20;
+
+/// Aggregated values of every node matched by a property pattern like `data.raw.*.*.max_health`.
+///
+/// Matches are referenced by their index in [`matches`](Self::matches).
+#[derive(Debug, Clone)]
+pub struct PropertyStats {
+    pub matches: Vec<QueryMatch>,
+    pub numbers: Option<NumberStats>,
+    /// Strings and booleans with the matches having them, most common first.
+    pub values: Vec<(Arc<str>, Vec<usize>)>,
+    /// Matches that are `null`, arrays or objects.
+    pub other: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct NumberStats {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub bins: Vec<Bin>,
+    /// Values outside of 1.5 times the interquartile range, sorted by value.
+    pub outliers: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Bin {
+    pub start: f64,
+    pub end: f64,
+    pub members: Vec<usize>,
+}
+
+impl PropertyStats {
+    pub fn collect(query: &Query, root: &DedupValue) -> Self {
+        let (matches, _) = query.run(root, usize::MAX);
+
+        let mut numbers = Vec::new();
+        let mut values = HashMap::<Arc<str>, Vec<usize>>::new();
+        let mut other = 0;
+        for (idx, m) in matches.iter().enumerate() {
+            match &m.value {
+                DedupValue::Number(n) => match n.as_f64() {
+                    Some(n) if n.is_finite() => numbers.push((n, idx)),
+                    _ => other += 1,
+                },
+                DedupValue::String(s) => values.entry(s.clone()).or_default().push(idx),
+                DedupValue::Bool(b) => values.entry(b.to_string().into()).or_default().push(idx),
+                _ => other += 1,
+            }
+        }
+
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort_unstable_by(|(a, a_idx), (b, b_idx)| {
+            b_idx.len().cmp(&a_idx.len()).then_with(|| a.cmp(b))
+        });
+
+        Self {
+            matches,
+            numbers: NumberStats::new(numbers),
+            values,
+            other,
+        }
+    }
+}
+
+impl NumberStats {
+    fn new(mut values: Vec<(f64, usize)>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        let count = values.len();
+        let min = values[0].0;
+        let max = values[count - 1].0;
+        #[allow(clippy::cast_precision_loss)]
+        let mean = values.iter().map(|(v, _)| v).sum::<f64>() / count as f64;
+        let median = quantile(&values, 0.5);
+
+        let q1 = quantile(&values, 0.25);
+        let q3 = quantile(&values, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let outliers = values
+            .iter()
+            .filter(|(v, _)| *v < q1 - fence || *v > q3 + fence)
+            .map(|(_, idx)| *idx)
+            .collect();
+
+        let bin_count = if min < max { HISTOGRAM_BINS } else { 1 };
+        #[allow(clippy::cast_precision_loss)]
+        let width = (max - min) / bin_count as f64;
+        let mut bins = (0..bin_count)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss)]
+                let start = (i as f64).mul_add(width, min);
+                Bin {
+                    start,
+                    end: start + width,
+                    members: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+        for (v, idx) in &values {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let bin = if width > 0.0 {
+                (((v - min) / width) as usize).min(bin_count - 1)
+            } else {
+                0
+            };
+            bins[bin].members.push(*idx);
+        }
+
+        Some(Self {
+            count,
+            min,
+            max,
+            mean,
+            median,
+            bins,
+            outliers,
+        })
+    }
+}
+
+/// Linear interpolation between the closest ranks of the sorted `values`.
+fn quantile(values: &[(f64, usize)], q: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let pos = (values.len() - 1) as f64 * q;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (pos.floor() as usize, pos.ceil() as usize);
+    let frac = pos - pos.floor();
+    (values[upper].0 - values[lower].0).mul_add(frac, values[lower].0)
+}
+
+/// Pattern matching the property at `path` on every prototype of the same type,
+/// e.g. `data.raw.unit.small-biter.max_health` becomes `data.raw.unit.*.max_health`.
+pub fn property_pattern(path: &NodePath) -> Option<String> {
+    let [type_, _, rest @ ..] = path.segments() else {
+        return None;
+    };
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut pattern = format!("data.raw.{}.*", quote_key(type_));
+    for key in rest {
+        pattern.push('.');
+        pattern.push_str(&quote_key(key));
+    }
+
+    Some(pattern)
+}
@@ -14,7 +14,7 @@ struct DocHelper {
 }
 
 impl DocHelper {
-    pub fn new(docs: PrototypeDoc) -> Self {
+    pub fn new(docs: PrototypeDoc, docs_url: &str) -> Self {
         let mut type2proto = HashMap::new();
         let mut name2proto = HashMap::new();
         let mut name2type = HashMap::new();
@@ -33,7 +33,7 @@ impl DocHelper {
             name2type.insert(type_.name.clone().into(), idx);
         }
 
-        let base_link = format!("https://lua-api.factorio.com/{}", docs.application_version).into();
+        let base_link = format!("{docs_url}/{}", docs.application_version).into();
 
         Self {
             docs,
@@ -101,6 +101,15 @@ impl DocHelper {
         }
     }
 
+    /// Every prototype and type that has its own docs page with the link to it.
+    pub fn doc_pages(&self) -> Vec<(Arc<str>, String)> {
+        self.name2proto
+            .keys()
+            .chain(self.name2type.keys())
+            .filter_map(|name| Some((name.clone(), self.get_doc_link(name)?)))
+            .collect()
+    }
+
     pub fn get_doc_link(&self, name: &str) -> Option<String> {
         if self.is_proto(name) {
             Some(format!("{}/prototypes/{name}.html", self.base_link))
@@ -224,8 +233,8 @@ pub struct TypeHelper {
 }
 
 impl TypeHelper {
-    pub fn new(docs: PrototypeDoc) -> Self {
-        let helper = DocHelper::new(docs);
+    pub fn new(docs: PrototypeDoc, docs_url: &str) -> Self {
+        let helper = DocHelper::new(docs, docs_url);
         Self {
             docs: Arc::new(helper),
             kind: CurrentType::DataRaw,
@@ -282,4 +291,62 @@ impl TypeHelper {
     pub fn get_doc_link(&self, name: Arc<str>) -> Option<String> {
         self.docs.get_doc_link(&name)
     }
+
+    pub fn doc_pages(&self) -> Vec<(Arc<str>, String)> {
+        self.docs.doc_pages()
+    }
+
+    /// Factorio version the docs were generated for.
+    pub fn docs_version(&self) -> String {
+        self.docs.docs.application_version.to_string()
+    }
+
+    /// Plain text version of the type, e.g. for screen readers.
+    pub fn describe(&self) -> Option<String> {
+        match &self.kind {
+            CurrentType::Unknown => None,
+            CurrentType::DataRaw => Some("data.raw".to_string()),
+            CurrentType::TypeOrProto(name) | CurrentType::BuiltIn(name) => Some(name.to_string()),
+            CurrentType::Complex(c) => Some(describe_complex(c)),
+        }
+    }
+}
+
+fn describe_type(t: &Type) -> String {
+    match t {
+        Type::Simple(name) => name.to_string(),
+        Type::Complex(c) => describe_complex(c),
+    }
+}
+
+fn describe_complex(c: &ComplexType) -> String {
+    use fapi_diff::format::prototype::LiteralValue;
+
+    let join = |types: &[Type], sep: &str| {
+        types
+            .iter()
+            .map(describe_type)
+            .collect::<Vec<_>>()
+            .join(sep)
+    };
+
+    match c {
+        ComplexType::Array { value } => format!("array of {}", describe_type(value)),
+        ComplexType::Dictionary { key, value } => format!(
+            "dictionary of {} to {}",
+            describe_type(key),
+            describe_type(value)
+        ),
+        ComplexType::Tuple { values } => format!("tuple of {}", join(values, ", ")),
+        ComplexType::Union { options, .. } => join(options, " or "),
+        ComplexType::Type { value, .. } => describe_type(value),
+        ComplexType::Literal(literal) => match &literal.value {
+            LiteralValue::Boolean(b) => b.to_string(),
+            LiteralValue::UInt(u) => u.to_string(),
+            LiteralValue::Int(i) => i.to_string(),
+            LiteralValue::Float(f) => f.to_string(),
+            LiteralValue::String(s) => format!("\"{s}\""),
+        },
+        _ => "table".to_string(),
+    }
 }
@@ -0,0 +1,77 @@
+use std::cmp::Ordering;
+
+/// Compares version strings like `2.0.10` component wise, numeric components by their value.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split(['.', '-', '+']);
+    let mut b_parts = b.split(['.', '-', '+']);
+
+    loop {
+        let ord = match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                // numbers are releases, text like `local` sorts after them
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+/// All processed versions of a single mod, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ModVersions {
+    pub name: String,
+    pub versions: Box<[String]>,
+    /// Number of prototypes in the latest dump, only counted by the server for local dumps.
+    ///
+    /// The mod search falls back to the counts of dumps opened in the browser before.
+    pub prototypes: Option<usize>,
+}
+
+impl ModVersions {
+    /// Groups `(name, version)` pairs by mod, keeping the order in which mods first appear.
+    pub fn group(pairs: impl IntoIterator<Item = (String, String)>) -> Vec<Self> {
+        let mut index = std::collections::HashMap::new();
+        let mut grouped: Vec<(String, Vec<String>)> = Vec::new();
+        for (name, version) in pairs {
+            match index.get(&name) {
+                Some(&idx) => grouped[idx].1.push(version),
+                None => {
+                    index.insert(name.clone(), grouped.len());
+                    grouped.push((name, vec![version]));
+                }
+            }
+        }
+
+        grouped
+            .into_iter()
+            .map(|(name, mut versions)| {
+                versions.sort_by(|a, b| compare_versions(b, a));
+                versions.dedup();
+                Self {
+                    name,
+                    versions: versions.into_boxed_slice(),
+                    prototypes: None,
+                }
+            })
+            .collect()
+    }
+
+    pub fn latest(&self) -> Option<&str> {
+        self.versions.first().map(String::as_str)
+    }
+
+    /// The version released before `version`.
+    pub fn previous(&self, version: &str) -> Option<&str> {
+        let idx = self.versions.iter().position(|v| v == version)?;
+        self.versions.get(idx + 1).map(String::as_str)
+    }
+}